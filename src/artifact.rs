@@ -0,0 +1,103 @@
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+use crate::config::{Artifact, ArtifactWhen};
+use crate::podman_command;
+use crate::podman_mount;
+
+enum ArtifactSource {
+    Host(PathBuf),
+    Container(String),
+}
+
+fn resolve_source(container_path: &str, root_dir: &Path, container_id: Option<&str>) -> Option<ArtifactSource> {
+    let path = Path::new(container_path);
+    if path.is_relative() {
+        return Some(ArtifactSource::Host(root_dir.join(path)));
+    }
+    if path.starts_with(root_dir) {
+        return Some(ArtifactSource::Host(path.to_path_buf()));
+    }
+    container_id.map(|id| ArtifactSource::Container(format!("{}:{}", id, container_path)))
+}
+
+fn copy_recursive(source: &Path, destination: &Path) -> Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(destination)
+            .with_context(|| format!("Failed to create directory: {}", destination.display()))?;
+        for entry in fs::read_dir(source)
+            .with_context(|| format!("Failed to read directory: {}", source.display()))?
+        {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    fs::copy(source, destination)
+        .with_context(|| format!("Failed to copy {} to {}", source.display(), destination.display()))?;
+    Ok(())
+}
+
+fn copy_from_container(source: &str, destination: &Path) -> Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    let output = podman_command::podman()
+        .args(["cp", source, &destination.display().to_string()])
+        .output()
+        .with_context(|| format!("Failed to execute podman cp for {}", source))?;
+
+    if !output.status.success() {
+        bail!("podman cp {} failed: {}", source, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+fn collect_artifact(artifact: &Artifact, root_dir: &Path, container_id: Option<&str>) -> Result<()> {
+    let destination = root_dir.join(&artifact.host_path);
+    match resolve_source(&artifact.container_path, root_dir, container_id) {
+        Some(ArtifactSource::Host(source)) => {
+            if !source.exists() {
+                bail!("{} does not exist", source.display());
+            }
+            copy_recursive(&source, &destination)
+        }
+        Some(ArtifactSource::Container(source)) => copy_from_container(&source, &destination),
+        None => bail!(
+            "{} is outside the mounted root directory; use container_mode = \"shared\" to copy it out of the container",
+            artifact.container_path
+        ),
+    }
+}
+
+pub fn collect_artifacts(artifacts: &[Artifact], root_dir: &Path, all_passed: bool, container_id: Option<&str>) -> Result<usize> {
+    let root_dir = podman_mount::absolute_root_dir(root_dir)?;
+    let mut collected = 0;
+
+    for artifact in artifacts {
+        if artifact.when == ArtifactWhen::Success && !all_passed {
+            info!("Skipping artifact {} because the run did not succeed", artifact.container_path);
+            continue;
+        }
+        match collect_artifact(artifact, &root_dir, container_id) {
+            Ok(()) => {
+                info!("Collected artifact {} -> {}", artifact.container_path, artifact.host_path);
+                collected += 1;
+            }
+            Err(e) => warn!("Failed to collect artifact {}: {:#}", artifact.container_path, e),
+        }
+    }
+
+    Ok(collected)
+}
+
+#[cfg(test)]
+#[path = "artifact/driver/collect/collect.rs"]
+mod driver_collect_collect;
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::artifact::collect_artifacts;
+    use crate::config::{ArtifactWhen, Config};
+    use crate::test::{process_test_report, DriverStatus, TestOptions};
+
+    fn load(temp_dir: &TempDir, toml_content: &str) -> Config {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        Config::load(&config_path).unwrap()
+    }
+
+    const CONFIG: &str = r#"
+[command.test]
+command = "sh"
+args = []
+artifacts = [
+    { container_path = "target/coverage", host_path = "reports/coverage" },
+    { container_path = "junit.xml", host_path = "reports/junit.xml", when = "always" },
+]
+"#;
+
+    #[test]
+    fn test_artifacts_parse_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, CONFIG);
+        let artifacts = &config.command.unwrap().test.unwrap().artifacts;
+
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0].container_path, "target/coverage");
+        assert_eq!(artifacts[0].when, ArtifactWhen::Success);
+        assert_eq!(artifacts[1].host_path, "reports/junit.xml");
+        assert_eq!(artifacts[1].when, ArtifactWhen::Always);
+    }
+
+    #[test]
+    fn test_artifacts_are_copied_to_host_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, CONFIG);
+        let artifacts = config.command.unwrap().test.unwrap().artifacts;
+        fs::create_dir_all(temp_dir.path().join("target/coverage/html")).unwrap();
+        fs::write(temp_dir.path().join("target/coverage/lcov.info"), "TN:").unwrap();
+        fs::write(temp_dir.path().join("target/coverage/html/index.html"), "<html>").unwrap();
+        fs::write(temp_dir.path().join("junit.xml"), "<testsuites/>").unwrap();
+
+        let collected = collect_artifacts(&artifacts, temp_dir.path(), true, None).unwrap();
+
+        assert_eq!(collected, 2);
+        assert_eq!(fs::read_to_string(temp_dir.path().join("reports/coverage/lcov.info")).unwrap(), "TN:");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("reports/coverage/html/index.html")).unwrap(), "<html>");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("reports/junit.xml")).unwrap(), "<testsuites/>");
+    }
+
+    #[test]
+    fn test_success_only_artifacts_are_skipped_after_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, CONFIG);
+        let artifacts = config.command.unwrap().test.unwrap().artifacts;
+        fs::create_dir_all(temp_dir.path().join("target/coverage")).unwrap();
+        fs::write(temp_dir.path().join("target/coverage/lcov.info"), "TN:").unwrap();
+        fs::write(temp_dir.path().join("junit.xml"), "<testsuites/>").unwrap();
+
+        let collected = collect_artifacts(&artifacts, temp_dir.path(), false, None).unwrap();
+
+        assert_eq!(collected, 1);
+        assert!(!temp_dir.path().join("reports/coverage").exists());
+        assert!(temp_dir.path().join("reports/junit.xml").exists());
+    }
+
+    #[test]
+    fn test_absolute_path_under_root_is_copied_from_mount() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("out.txt");
+        fs::write(&source, "result").unwrap();
+        let config = load(&temp_dir, &format!(
+            "[command.test]\ncommand = \"sh\"\nargs = []\nartifacts = [{{ container_path = \"{}\", host_path = \"copied.txt\" }}]\n",
+            source.display()
+        ));
+        let artifacts = config.command.unwrap().test.unwrap().artifacts;
+
+        let collected = collect_artifacts(&artifacts, temp_dir.path(), true, None).unwrap();
+
+        assert_eq!(collected, 1);
+        assert_eq!(fs::read_to_string(temp_dir.path().join("copied.txt")).unwrap(), "result");
+    }
+
+    #[test]
+    fn test_missing_or_unreachable_artifacts_are_not_fatal() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, r#"
+[command.test]
+command = "sh"
+args = []
+artifacts = [
+    { container_path = "missing.xml", host_path = "reports/missing.xml" },
+    { container_path = "/outside/root/report.xml", host_path = "reports/report.xml" },
+]
+"#);
+        let artifacts = config.command.unwrap().test.unwrap().artifacts;
+
+        let collected = collect_artifacts(&artifacts, temp_dir.path(), true, None).unwrap();
+
+        assert_eq!(collected, 0);
+        assert!(!temp_dir.path().join("reports").exists());
+    }
+
+    #[test]
+    fn test_success_only_artifacts_are_skipped_after_config_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, r#"
+[[driver_patterns]]
+pattern = "checks/.+\\.sh"
+testcase = "$1"
+
+[command.test]
+command = "sh"
+args = ["{driver_file}"]
+require_testcase = true
+artifacts = [
+    { container_path = "target/coverage", host_path = "reports/coverage" },
+    { container_path = "junit.xml", host_path = "reports/junit.xml", when = "always" },
+]
+"#);
+        fs::create_dir_all(temp_dir.path().join("checks")).unwrap();
+        fs::write(temp_dir.path().join("checks/unmapped.sh"), "exit 0\n").unwrap();
+        fs::create_dir_all(temp_dir.path().join("target/coverage")).unwrap();
+        fs::write(temp_dir.path().join("target/coverage/lcov.info"), "TN:").unwrap();
+        fs::write(temp_dir.path().join("junit.xml"), "<testsuites/>").unwrap();
+
+        let report = process_test_report(&config, temp_dir.path(), &TestOptions::default()).unwrap();
+
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].status, DriverStatus::ConfigError);
+        assert!(!temp_dir.path().join("reports/coverage").exists());
+        assert!(temp_dir.path().join("reports/junit.xml").exists());
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::{Context, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+fn temp_path(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Cannot write to a path without a file name: {}", path.display()))?;
+    let mut temp_name = file_name.to_os_string();
+    temp_name.push(format!(".{}.tmp", std::process::id()));
+    Ok(path.with_file_name(temp_name))
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(bytes).with_context(|| format!("Failed to write {}", path.display()))?;
+    file.sync_all().with_context(|| format!("Failed to sync {}", path.display()))
+}
+
+pub fn atomic_write(path: &Path, bytes: impl AsRef<[u8]>) -> Result<()> {
+    let temp_path = temp_path(path)?;
+    let result = write_synced(&temp_path, bytes.as_ref()).and_then(|()| {
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))
+    });
+    if result.is_err() {
+        fs::remove_file(&temp_path).ok();
+        return result;
+    }
+
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        File::open(parent).and_then(|dir| dir.sync_all()).ok();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "atomic_file/driver/write/write.rs"]
+mod driver_write_write;
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::atomic_file::atomic_write;
+
+    #[test]
+    fn test_atomic_write_creates_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.toml");
+
+        atomic_write(&path, "a = 1\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1\n");
+    }
+
+    #[test]
+    fn test_atomic_write_replaces_existing_file_without_leftovers() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.toml");
+        fs::write(&path, "a = 1\nb = 2\n").unwrap();
+
+        atomic_write(&path, "a = 3\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 3\n");
+        let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(entries, vec!["state.toml"]);
+    }
+
+    #[test]
+    fn test_failed_rename_keeps_original_and_removes_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state");
+        fs::create_dir_all(path.join("occupied")).unwrap();
+
+        assert!(atomic_write(&path, "a = 1\n").is_err());
+
+        assert!(path.join("occupied").is_dir());
+        let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(entries, vec!["state"]);
+    }
+}
@@ -0,0 +1,360 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use log::{info, warn};
+use crate::atomic_file::atomic_write;
+use crate::config::{Config, InvalidConfig};
+use crate::test::{check_driver_results, passed_durations, run_driver_suite, DriverSuite, TestOptions, TestsFailed};
+
+const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct BenchOptions {
+    pub compare: bool,
+    pub threshold_percent: Option<f64>,
+    pub baseline: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum HistoryEntry {
+    Timestamp(u64, PathBuf),
+    Named(String, PathBuf),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+struct BenchHistory {
+    #[serde(default)]
+    durations: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchDelta {
+    driver_file: String,
+    previous: f64,
+    current: f64,
+    delta_percent: f64,
+}
+
+fn history_dir(root_dir: &Path) -> PathBuf {
+    root_dir.join(".overcode").join("bench_history")
+}
+
+fn validate_tag_name(name: &str) -> anyhow::Result<()> {
+    let valid_chars = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if name.is_empty() || !valid_chars || name.parse::<u64>().is_ok() {
+        return Err(InvalidConfig(format!(
+            "Invalid history tag name: '{}'. Use [a-zA-Z0-9_-]+ and at least one non-digit",
+            name
+        )).into());
+    }
+    Ok(())
+}
+
+fn iter_history(dir: &Path) -> anyhow::Result<Vec<HistoryEntry>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read bench history: {}", dir.display()))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|extension| extension != "toml") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()).map(String::from) else {
+            continue;
+        };
+        if let Ok(timestamp) = stem.parse::<u64>() {
+            entries.push(HistoryEntry::Timestamp(timestamp, path));
+        } else if validate_tag_name(&stem).is_ok() && path.is_symlink() {
+            entries.push(HistoryEntry::Named(stem, path));
+        }
+    }
+    Ok(entries)
+}
+
+fn parse_history(path: &Path, content: &str) -> anyhow::Result<BenchHistory> {
+    toml::from_str(content)
+        .with_context(|| format!("Failed to parse bench history: {}", path.display()))
+}
+
+fn read_history(path: &Path) -> anyhow::Result<BenchHistory> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read bench history: {}", path.display()))?;
+    parse_history(path, &content)
+}
+
+fn quarantine_history(path: &Path) {
+    let mut corrupt_name = path.file_name().unwrap_or_default().to_os_string();
+    corrupt_name.push(".corrupt");
+    let corrupt_path = path.with_file_name(corrupt_name);
+    match fs::rename(path, &corrupt_path) {
+        Ok(()) => warn!("Moved corrupt bench history to {}", corrupt_path.display()),
+        Err(err) => warn!("Failed to move corrupt bench history {}: {}", path.display(), err),
+    }
+}
+
+fn snapshot_paths(dir: &Path) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    Ok(iter_history(dir)?
+        .into_iter()
+        .filter_map(|entry| match entry {
+            HistoryEntry::Timestamp(timestamp, path) => Some((timestamp, path)),
+            HistoryEntry::Named(..) => None,
+        })
+        .collect())
+}
+
+fn snapshots_newest_first(dir: &Path) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    let mut snapshots = snapshot_paths(dir)?;
+    snapshots.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
+    Ok(snapshots)
+}
+
+fn latest_history(dir: &Path) -> anyhow::Result<Option<BenchHistory>> {
+    for (_, path) in snapshots_newest_first(dir)? {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                warn!("Skipping unreadable bench history {}: {}", path.display(), err);
+                continue;
+            }
+        };
+        match parse_history(&path, &content) {
+            Ok(history) => return Ok(Some(history)),
+            Err(err) => {
+                warn!("{:#}", err);
+                quarantine_history(&path);
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn copy_if_missing(source: &Path, dest_dir: &Path) -> anyhow::Result<bool> {
+    let Some(file_name) = source.file_name() else {
+        return Ok(false);
+    };
+    let dest = dest_dir.join(file_name);
+    if dest.symlink_metadata().is_ok() {
+        return Ok(false);
+    }
+    fs::create_dir_all(dest_dir)
+        .with_context(|| format!("Failed to create bench history directory: {}", dest_dir.display()))?;
+    let content = fs::read(source)
+        .with_context(|| format!("Failed to read bench history: {}", source.display()))?;
+    atomic_write(&dest, content)
+        .with_context(|| format!("Failed to copy {} to {}", source.display(), dest.display()))?;
+    Ok(true)
+}
+
+pub fn export_history(root_dir: &Path, dest: &Path) -> anyhow::Result<()> {
+    let latest = snapshots_newest_first(&history_dir(root_dir))?.into_iter().next();
+    let Some((timestamp, path)) = latest else {
+        info!("No bench history to export");
+        return Ok(());
+    };
+
+    let dest_dir = history_dir(dest);
+    if copy_if_missing(&path, &dest_dir)? {
+        info!("Exported bench history {} to {}", timestamp, dest_dir.display());
+    } else {
+        info!("Bench history {} is already present in {}", timestamp, dest_dir.display());
+    }
+    Ok(())
+}
+
+pub fn import_history(root_dir: &Path, source: &Path) -> anyhow::Result<()> {
+    let source_dir = history_dir(source);
+    if !source_dir.is_dir() {
+        return Err(InvalidConfig(format!("No bench history found in {}", source_dir.display())).into());
+    }
+
+    let dest_dir = history_dir(root_dir);
+    let mut imported = 0;
+    let mut skipped = 0;
+    for (_, path) in snapshot_paths(&source_dir)? {
+        if copy_if_missing(&path, &dest_dir)? {
+            imported += 1;
+        } else {
+            skipped += 1;
+        }
+    }
+    info!("Imported {} bench history snapshot(s), skipped {} already present", imported, skipped);
+    Ok(())
+}
+
+fn load_history_at(dir: &Path, reference: &str) -> anyhow::Result<BenchHistory> {
+    if reference.parse::<u64>().is_err() {
+        validate_tag_name(reference)?;
+    }
+    let found = iter_history(dir)?.into_iter().find_map(|entry| match entry {
+        HistoryEntry::Timestamp(timestamp, path) if timestamp.to_string() == reference => Some(path),
+        HistoryEntry::Named(name, path) if name == reference => Some(path),
+        _ => None,
+    });
+    let path = found.ok_or_else(|| InvalidConfig(format!("Bench history not found: {}", reference)))?;
+    read_history(&path)
+}
+
+pub fn tag_history(root_dir: &Path, timestamp: u64, name: &str) -> anyhow::Result<()> {
+    validate_tag_name(name)?;
+    let dir = history_dir(root_dir);
+    let target = format!("{}.toml", timestamp);
+    if !dir.join(&target).is_file() {
+        return Err(InvalidConfig(format!("Bench history not found: {}", timestamp)).into());
+    }
+
+    let link = dir.join(format!("{}.toml", name));
+    if link.symlink_metadata().is_ok() {
+        return Err(InvalidConfig(format!(
+            "Bench history tag already exists: {}. Remove it with 'history untag {}' first",
+            name, name
+        )).into());
+    }
+    std::os::unix::fs::symlink(&target, &link)
+        .with_context(|| format!("Failed to create bench history tag: {}", link.display()))?;
+    info!("Tagged bench history {} as {}", timestamp, name);
+    Ok(())
+}
+
+pub fn untag_history(root_dir: &Path, name: &str) -> anyhow::Result<()> {
+    validate_tag_name(name)?;
+    let link = history_dir(root_dir).join(format!("{}.toml", name));
+    if !link.is_symlink() {
+        return Err(InvalidConfig(format!("Bench history tag not found: {}", name)).into());
+    }
+    fs::remove_file(&link)
+        .with_context(|| format!("Failed to remove bench history tag: {}", link.display()))?;
+    info!("Removed bench history tag {}", name);
+    Ok(())
+}
+
+fn write_history(dir: &Path, timestamp: u64, history: &BenchHistory) -> anyhow::Result<PathBuf> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create bench history directory: {}", dir.display()))?;
+    let path = dir.join(format!("{}.toml", timestamp));
+    let content = toml::to_string(history).context("Failed to serialize bench history")?;
+    atomic_write(&path, content)
+        .with_context(|| format!("Failed to write bench history: {}", path.display()))?;
+    Ok(path)
+}
+
+fn compare_histories(previous: &BenchHistory, current: &BenchHistory) -> Vec<BenchDelta> {
+    current
+        .durations
+        .iter()
+        .filter_map(|(driver_file, &current)| {
+            let &previous = previous.durations.get(driver_file)?;
+            let delta_percent = if previous > 0.0 {
+                (current - previous) / previous * 100.0
+            } else {
+                0.0
+            };
+            Some(BenchDelta {
+                driver_file: driver_file.clone(),
+                previous,
+                current,
+                delta_percent,
+            })
+        })
+        .collect()
+}
+
+fn format_comparison(deltas: &[BenchDelta], threshold_percent: f64) -> String {
+    let mut table = format!("{:>9}  {:>9}  {:>8}  BENCH\n", "PREVIOUS", "CURRENT", "DELTA");
+    for delta in deltas {
+        let marker = if delta.delta_percent > threshold_percent { "  REGRESSION" } else { "" };
+        table.push_str(&format!(
+            "{:>8.2}s  {:>8.2}s  {:>+7.1}%  {}{}\n",
+            delta.previous,
+            delta.current,
+            delta.delta_percent,
+            delta.driver_file,
+            marker
+        ));
+    }
+    table
+}
+
+pub fn process_bench(
+    config: &Config,
+    root_dir: &Path,
+    options: &TestOptions,
+    bench_options: &BenchOptions,
+) -> anyhow::Result<()> {
+    let run_bench = config.command
+        .as_ref()
+        .and_then(|c| c.bench.as_ref())
+        .ok_or_else(|| InvalidConfig("[command.bench] section not found in overcode.toml".to_string()))?;
+    let suite = DriverSuite {
+        section: "command.bench",
+        patterns: &config.bench_patterns,
+        command: run_bench,
+    };
+
+    let dir = history_dir(root_dir);
+    let compare = bench_options.compare || bench_options.baseline.is_some();
+    let previous = match bench_options.baseline {
+        Some(ref reference) => Some(load_history_at(&dir, reference)?),
+        None if compare => latest_history(&dir)?,
+        None => None,
+    };
+
+    let results = run_driver_suite(config, root_dir, &suite, options)?;
+    if results.is_empty() {
+        return Ok(());
+    }
+
+    let current = BenchHistory {
+        durations: passed_durations(&results)
+            .into_iter()
+            .map(|(driver_file, duration)| (driver_file, duration.as_secs_f64()))
+            .collect(),
+    };
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System clock is before the UNIX epoch")?
+        .as_secs();
+    let path = write_history(&dir, timestamp, &current)?;
+    info!("Bench durations recorded in {}", path.display());
+
+    check_driver_results(&results, options)?;
+
+    if !compare {
+        return Ok(());
+    }
+    let Some(previous) = previous else {
+        info!("No previous bench history to compare against");
+        return Ok(());
+    };
+
+    let threshold_percent = bench_options.threshold_percent.unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
+    let deltas = compare_histories(&previous, &current);
+    info!("Bench comparison:\n{}", format_comparison(&deltas, threshold_percent));
+
+    let regressions = deltas.iter().filter(|delta| delta.delta_percent > threshold_percent).count();
+    if regressions > 0 {
+        return Err(TestsFailed(format!(
+            "{} bench(es) regressed by more than {:.1}% compared to the previous run",
+            regressions,
+            threshold_percent
+        )).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "bench/driver/history/history.rs"]
+mod driver_history_history;
+
+#[cfg(test)]
+#[path = "bench/driver/export/export.rs"]
+mod driver_export_export;
+
+#[cfg(test)]
+#[path = "bench/driver/tag/tag.rs"]
+mod driver_tag_tag;
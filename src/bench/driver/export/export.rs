@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::bench::{
+        export_history, history_dir, import_history, load_history_at, tag_history, write_history,
+        BenchHistory,
+    };
+
+    fn history(secs: f64) -> BenchHistory {
+        BenchHistory {
+            durations: BTreeMap::from([("src/a/bench/x/sort.rs".to_string(), secs)]),
+        }
+    }
+
+    fn file_names(dir: &std::path::Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_export_copies_only_latest_snapshot() {
+        let project = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        write_history(&history_dir(project.path()), 1_700_000_000, &history(1.0)).unwrap();
+        write_history(&history_dir(project.path()), 1_700_000_100, &history(2.0)).unwrap();
+        tag_history(project.path(), 1_700_000_000, "baseline").unwrap();
+
+        export_history(project.path(), dest.path()).unwrap();
+
+        assert_eq!(file_names(&history_dir(dest.path())), vec!["1700000100.toml"]);
+        assert_eq!(load_history_at(&history_dir(dest.path()), "1700000100").unwrap(), history(2.0));
+    }
+
+    #[test]
+    fn test_export_keeps_existing_destination_snapshot() {
+        let project = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        write_history(&history_dir(project.path()), 1_700_000_000, &history(1.0)).unwrap();
+        write_history(&history_dir(dest.path()), 1_700_000_000, &history(9.0)).unwrap();
+
+        export_history(project.path(), dest.path()).unwrap();
+
+        assert_eq!(load_history_at(&history_dir(dest.path()), "1700000000").unwrap(), history(9.0));
+    }
+
+    #[test]
+    fn test_export_without_history_is_a_no_op() {
+        let project = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+
+        export_history(project.path(), dest.path()).unwrap();
+
+        assert!(!history_dir(dest.path()).exists());
+    }
+
+    #[test]
+    fn test_import_merges_without_overwriting() {
+        let project = TempDir::new().unwrap();
+        let source = TempDir::new().unwrap();
+        write_history(&history_dir(project.path()), 1_700_000_000, &history(1.0)).unwrap();
+        write_history(&history_dir(source.path()), 1_700_000_000, &history(5.0)).unwrap();
+        write_history(&history_dir(source.path()), 1_700_000_100, &history(2.0)).unwrap();
+        tag_history(source.path(), 1_700_000_100, "release").unwrap();
+
+        import_history(project.path(), source.path()).unwrap();
+
+        let dir = history_dir(project.path());
+        assert_eq!(file_names(&dir), vec!["1700000000.toml", "1700000100.toml"]);
+        assert_eq!(load_history_at(&dir, "1700000000").unwrap(), history(1.0));
+        assert_eq!(load_history_at(&dir, "1700000100").unwrap(), history(2.0));
+    }
+
+    #[test]
+    fn test_import_requires_history_in_source() {
+        let project = TempDir::new().unwrap();
+        let source = TempDir::new().unwrap();
+
+        assert!(import_history(project.path(), source.path()).is_err());
+    }
+}
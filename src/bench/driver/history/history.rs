@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::bench::{
+        compare_histories, format_comparison, latest_history, process_bench, write_history,
+        BenchDelta, BenchHistory, BenchOptions,
+    };
+    use crate::config::Config;
+    use crate::test::TestOptions;
+
+    fn history(entries: &[(&str, f64)]) -> BenchHistory {
+        BenchHistory {
+            durations: entries
+                .iter()
+                .map(|(driver_file, secs)| (driver_file.to_string(), *secs))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_history_round_trip_picks_latest_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("bench_history");
+        let older = history(&[("src/a/bench/x/sort.rs", 1.0)]);
+        let newer = history(&[("src/a/bench/x/sort.rs", 1.5)]);
+
+        write_history(&dir, 1_700_000_100, &newer).unwrap();
+        write_history(&dir, 1_700_000_000, &older).unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        assert_eq!(latest_history(&dir).unwrap(), Some(newer));
+    }
+
+    #[test]
+    fn test_latest_history_quarantines_truncated_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("bench_history");
+        let older = history(&[("src/a/bench/x/sort.rs", 1.0)]);
+        write_history(&dir, 1_700_000_000, &older).unwrap();
+        fs::write(dir.join("1700000100.toml"), "[durations]\n\"src/a/bench/x/sort.rs\" = ").unwrap();
+
+        assert_eq!(latest_history(&dir).unwrap(), Some(older));
+        assert!(!dir.join("1700000100.toml").exists());
+        assert!(dir.join("1700000100.toml.corrupt").is_file());
+        assert_eq!(latest_history(&dir).unwrap(), Some(history(&[("src/a/bench/x/sort.rs", 1.0)])));
+    }
+
+    #[test]
+    fn test_latest_history_with_only_corrupt_snapshots_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("bench_history");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("1700000000.toml"), "durations = [").unwrap();
+        fs::write(dir.join("1700000100.toml"), "durations = 3").unwrap();
+
+        assert_eq!(latest_history(&dir).unwrap(), None);
+        assert!(dir.join("1700000000.toml.corrupt").is_file());
+        assert!(dir.join("1700000100.toml.corrupt").is_file());
+    }
+
+    #[test]
+    fn test_write_history_leaves_no_temp_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("bench_history");
+
+        write_history(&dir, 1_700_000_000, &history(&[("src/a/bench/x/sort.rs", 1.0)])).unwrap();
+
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(entries, vec!["1700000000.toml"]);
+    }
+
+    #[test]
+    fn test_latest_history_without_directory() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert_eq!(latest_history(&temp_dir.path().join("missing")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_compare_histories_reports_deltas_for_common_benches() {
+        let previous = history(&[("src/a/bench/x/parse.rs", 2.0), ("src/a/bench/x/sort.rs", 1.0)]);
+        let current = history(&[("src/a/bench/x/new.rs", 0.5), ("src/a/bench/x/sort.rs", 1.25)]);
+
+        let deltas = compare_histories(&previous, &current);
+
+        assert_eq!(
+            deltas,
+            vec![BenchDelta {
+                driver_file: "src/a/bench/x/sort.rs".to_string(),
+                previous: 1.0,
+                current: 1.25,
+                delta_percent: 25.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_format_comparison_marks_regressions_over_threshold() {
+        let previous = history(&[("src/a/bench/x/fast.rs", 2.0), ("src/a/bench/x/slow.rs", 1.0)]);
+        let current = history(&[("src/a/bench/x/fast.rs", 1.9), ("src/a/bench/x/slow.rs", 1.25)]);
+
+        let table = format_comparison(&compare_histories(&previous, &current), 10.0);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], " PREVIOUS    CURRENT     DELTA  BENCH");
+        assert_eq!(lines[1], "    2.00s      1.90s     -5.0%  src/a/bench/x/fast.rs");
+        assert_eq!(lines[2], "    1.00s      1.25s    +25.0%  src/a/bench/x/slow.rs  REGRESSION");
+    }
+
+    #[test]
+    fn test_process_bench_requires_bench_section() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+        let config = Config::load(&config_path).unwrap();
+
+        let result = process_bench(&config, temp_dir.path(), &TestOptions::default(), &BenchOptions::default());
+
+        assert!(result.unwrap_err().to_string().contains("[command.bench]"));
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+    use crate::bench::{
+        history_dir, iter_history, latest_history, load_history_at, tag_history, untag_history,
+        write_history, BenchHistory, HistoryEntry,
+    };
+
+    fn history(secs: f64) -> BenchHistory {
+        BenchHistory {
+            durations: BTreeMap::from([("src/a/bench/x/sort.rs".to_string(), secs)]),
+        }
+    }
+
+    #[test]
+    fn test_tag_creates_named_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = history_dir(temp_dir.path());
+        write_history(&dir, 1_700_000_000, &history(1.0)).unwrap();
+
+        tag_history(temp_dir.path(), 1_700_000_000, "release-1_0").unwrap();
+
+        let link = dir.join("release-1_0.toml");
+        assert!(link.is_symlink());
+        let mut entries = iter_history(&dir).unwrap();
+        entries.sort_by_key(|entry| matches!(entry, HistoryEntry::Named(..)));
+        assert_eq!(entries, vec![
+            HistoryEntry::Timestamp(1_700_000_000, dir.join("1700000000.toml")),
+            HistoryEntry::Named("release-1_0".to_string(), link),
+        ]);
+    }
+
+    #[test]
+    fn test_load_history_at_accepts_timestamp_or_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = history_dir(temp_dir.path());
+        write_history(&dir, 1_700_000_000, &history(1.0)).unwrap();
+        write_history(&dir, 1_700_000_100, &history(2.0)).unwrap();
+        tag_history(temp_dir.path(), 1_700_000_000, "baseline").unwrap();
+
+        assert_eq!(load_history_at(&dir, "baseline").unwrap(), history(1.0));
+        assert_eq!(load_history_at(&dir, "1700000100").unwrap(), history(2.0));
+        assert_eq!(latest_history(&dir).unwrap(), Some(history(2.0)));
+        assert!(load_history_at(&dir, "missing").is_err());
+    }
+
+    #[test]
+    fn test_tag_rejects_existing_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = history_dir(temp_dir.path());
+        write_history(&dir, 1_700_000_000, &history(1.0)).unwrap();
+        write_history(&dir, 1_700_000_100, &history(2.0)).unwrap();
+        tag_history(temp_dir.path(), 1_700_000_000, "baseline").unwrap();
+
+        let err = tag_history(temp_dir.path(), 1_700_000_100, "baseline").unwrap_err();
+
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(load_history_at(&dir, "baseline").unwrap(), history(1.0));
+    }
+
+    #[test]
+    fn test_tag_rejects_invalid_name_and_missing_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        write_history(&history_dir(temp_dir.path()), 1_700_000_000, &history(1.0)).unwrap();
+
+        assert!(tag_history(temp_dir.path(), 1_700_000_000, "bad/name").is_err());
+        assert!(tag_history(temp_dir.path(), 1_700_000_000, "bad.name").is_err());
+        assert!(tag_history(temp_dir.path(), 1_700_000_000, "").is_err());
+        assert!(tag_history(temp_dir.path(), 1_700_000_000, "123").is_err());
+        assert!(tag_history(temp_dir.path(), 1_600_000_000, "old").is_err());
+    }
+
+    #[test]
+    fn test_untag_removes_only_the_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = history_dir(temp_dir.path());
+        write_history(&dir, 1_700_000_000, &history(1.0)).unwrap();
+        tag_history(temp_dir.path(), 1_700_000_000, "baseline").unwrap();
+
+        untag_history(temp_dir.path(), "baseline").unwrap();
+
+        assert!(!dir.join("baseline.toml").exists());
+        assert!(dir.join("1700000000.toml").is_file());
+        assert!(untag_history(temp_dir.path(), "baseline").is_err());
+        assert!(untag_history(temp_dir.path(), "1700000000").is_err());
+    }
+}
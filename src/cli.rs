@@ -1,11 +1,24 @@
 use anyhow::{Context, Result};
+use log::LevelFilter;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::podman_install::StatusFormat;
+use crate::test::OutputFormat;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
     Init,
     Test,
     Run,
+    Bench,
+    Gc,
+    Serve,
+    MigrateConfig,
+    HistoryTag { timestamp: u64, name: String },
+    HistoryUntag { name: String },
+    Export { output: PathBuf },
+    Import { from: PathBuf },
+    Index { watch: bool, debounce: Duration },
 }
 
 #[derive(Debug)]
@@ -14,6 +27,43 @@ pub struct Cli {
     pub root_dir: PathBuf,
     pub config_path: PathBuf,
     pub extra_args: Vec<String>,
+    pub no_gitignore: bool,
+    pub log_level: Option<LevelFilter>,
+    pub check_command: bool,
+    pub run_name: Option<String>,
+    pub gc_volumes: bool,
+    pub gc_keep_days: Option<u32>,
+    pub gc_keep_count: Option<usize>,
+    pub dry_run: bool,
+    pub tags: Vec<String>,
+    pub deadline: Option<Duration>,
+    pub recursive: bool,
+    pub fail_fast: bool,
+    pub no_init: bool,
+    pub no_podman: bool,
+    pub format: OutputFormat,
+    pub jobs: Option<usize>,
+    pub compare: bool,
+    pub threshold: Option<f64>,
+    pub baseline: Option<String>,
+    pub history: Option<usize>,
+    pub output_format: StatusFormat,
+    pub timeout: Option<Duration>,
+    pub include_excluded: bool,
+    pub refresh_images: bool,
+}
+
+const CONFIG_ENV: &str = "OVERCODE_CONFIG";
+const DEFAULT_HISTORY_COUNT: usize = 10;
+
+fn select_config_file<'a>(
+    explicit: Option<&'a str>,
+    positional: Option<&'a str>,
+    env: Option<&'a str>,
+) -> Option<&'a str> {
+    explicit
+        .or(positional)
+        .or(env.filter(|value| !value.is_empty()))
 }
 
 fn find_config_dir(config_path: &Path) -> Result<PathBuf> {
@@ -53,19 +103,139 @@ fn find_config_in_current_dir() -> Result<PathBuf> {
     Ok(config_path)
 }
 
+fn parse_log_level(global_flags: &[String]) -> Result<Option<LevelFilter>> {
+    let mut quiet = false;
+    let mut verbosity = 0;
+
+    for flag in global_flags {
+        match flag.as_str() {
+            "-q" | "--quiet" => quiet = true,
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            _ => anyhow::bail!("Unknown option: {}. Global options are -q/--quiet, -v and -vv", flag),
+        }
+    }
+
+    if quiet && verbosity > 0 {
+        anyhow::bail!("--quiet cannot be combined with -v/-vv");
+    }
+
+    Ok(match (quiet, verbosity) {
+        (true, _) => Some(LevelFilter::Error),
+        (false, 0) => None,
+        (false, 1) => Some(LevelFilter::Debug),
+        (false, _) => Some(LevelFilter::Trace),
+    })
+}
+
+fn positional_args(args: &[String]) -> Vec<String> {
+    let mut positionals = Vec::new();
+    let mut iter = args.iter().peekable();
+
+    while let Some(arg) = iter.next() {
+        if arg == "--config" || arg == "--tag" || arg == "--deadline" || arg == "--format" || arg == "--jobs" || arg == "--threshold" || arg == "--output-format" || arg == "--timeout" || arg == "--keep-days" || arg == "--keep-count" || arg == "--baseline" || arg == "--output" || arg == "--from" || arg == "--debounce" {
+            iter.next();
+        } else if arg == "--history" {
+            iter.next_if(|value| value.parse::<usize>().is_ok());
+        } else if !arg.starts_with('-') {
+            positionals.push(arg.clone());
+        }
+    }
+
+    positionals
+}
+
+fn history_count(args: &[String]) -> Option<usize> {
+    let history_pos = args.iter().position(|arg| arg == "--history")?;
+    Some(
+        args.get(history_pos + 1)
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_HISTORY_COUNT),
+    )
+}
+
+fn option_values(args: &[String], name: &str) -> Result<Vec<String>> {
+    let mut values = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == name {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("{} option requires a value", name))?;
+            values.push(value.clone());
+        }
+    }
+
+    Ok(values)
+}
+
+fn parse_history_command(args: &[String]) -> Result<Command> {
+    const USAGE: &str = "Usage: history tag <timestamp> <name> | history untag <name>";
+    let positionals = positional_args(args);
+
+    match positionals.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["tag", timestamp, name] => Ok(Command::HistoryTag {
+            timestamp: timestamp
+                .parse::<u64>()
+                .with_context(|| format!("history tag expects a numeric timestamp, got: {}", timestamp))?,
+            name: name.to_string(),
+        }),
+        ["untag", name] => Ok(Command::HistoryUntag { name: name.to_string() }),
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
 impl Cli {
     pub fn parse() -> Result<Self> {
-        let args: Vec<String> = std::env::args().collect();
-        
+        Self::parse_from(std::env::args().collect())
+    }
+
+    pub fn parse_from(args: Vec<String>) -> Result<Self> {
+        let global_count = args.iter().skip(1).take_while(|arg| arg.starts_with('-')).count();
+        let log_level = parse_log_level(args.get(1..1 + global_count).unwrap_or(&[]))?;
+        let args: Vec<String> = args
+            .iter()
+            .take(1)
+            .chain(args.iter().skip(1 + global_count))
+            .cloned()
+            .collect();
+
         if args.len() < 2 {
-            anyhow::bail!("Usage: {} <command> [--config <config_file>] [-- extra_args...]\n  For 'run' command, you can pass additional arguments after '--'", args[0]);
+            anyhow::bail!("Usage: {} [-q|-v|-vv] <command> [<config_file> | --config <config_file>] [-- extra_args...]\n  For 'run' command, you can pass additional arguments after '--' and select [command.custom.<name>] with 'run <name>'; 'run --history [N]' shows the last N recorded runs", args.first().map(String::as_str).unwrap_or("overcode"));
         }
 
         let command = match args[1].as_str() {
             "init" => Command::Init,
             "test" => Command::Test,
             "run" => Command::Run,
-            _ => anyhow::bail!("Unknown command: {}. Use 'init', 'test', or 'run'", args[1]),
+            "bench" => Command::Bench,
+            "gc" => Command::Gc,
+            "serve" => Command::Serve,
+            "migrate-config" => Command::MigrateConfig,
+            "history" => parse_history_command(&args[2..])?,
+            "export" => Command::Export {
+                output: option_values(&args, "--output")?.last().map(PathBuf::from).unwrap_or_else(|| PathBuf::from(".")),
+            },
+            "import" => Command::Import {
+                from: option_values(&args, "--from")?
+                    .last()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| anyhow::anyhow!("import requires --from <dir>"))?,
+            },
+            "index" => Command::Index {
+                watch: args.iter().any(|arg| arg == "--watch"),
+                debounce: option_values(&args, "--debounce")?
+                    .last()
+                    .map(|millis| {
+                        millis.parse::<u64>()
+                            .map(Duration::from_millis)
+                            .with_context(|| format!("--debounce expects a number of milliseconds, got: {}", millis))
+                    })
+                    .transpose()?
+                    .unwrap_or(crate::index::DEFAULT_DEBOUNCE),
+            },
+            _ => anyhow::bail!("Unknown command: {}. Use 'init', 'test', 'run', 'bench', 'gc', 'serve', 'index', 'history', 'export', 'import', or 'migrate-config'", args[1]),
         };
 
         let (args_for_config, extra_args) = if matches!(command, Command::Run) {
@@ -88,11 +258,30 @@ impl Cli {
             (&args[..], Vec::new())
         };
 
-        let config_path = if let Some(config_pos) = args_for_config.iter().position(|arg| arg == "--config") {
-            if config_pos + 1 >= args_for_config.len() {
-                anyhow::bail!("--config option requires a file path");
-            }
-            let config_file = &args_for_config[config_pos + 1];
+        let recursive = matches!(command, Command::Test) && args_for_config.iter().any(|arg| arg == "--recursive");
+
+        let explicit_config = match args_for_config.iter().position(|arg| arg == "--config") {
+            Some(config_pos) => Some(
+                args_for_config
+                    .get(config_pos + 1)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("--config option requires a file path"))?,
+            ),
+            None => None,
+        };
+        let positional_config = args_for_config
+            .get(2)
+            .filter(|arg| !arg.starts_with('-'))
+            .filter(|_| !matches!(command, Command::HistoryTag { .. } | Command::HistoryUntag { .. }))
+            .filter(|arg| !matches!(command, Command::Run) || arg.ends_with(".toml"))
+            .cloned();
+        let env_config = std::env::var(CONFIG_ENV).ok();
+
+        let config_path = if let Some(config_file) = select_config_file(
+            explicit_config.as_deref(),
+            positional_config.as_deref(),
+            env_config.as_deref(),
+        ) {
             let config_path = PathBuf::from(config_file);
             if matches!(command, Command::Init) {
                 config_path
@@ -100,7 +289,7 @@ impl Cli {
                 find_config_dir(&config_path)?
             }
         } else {
-            if matches!(command, Command::Init) {
+            if matches!(command, Command::Init) || recursive {
                 let current_dir = std::env::current_dir()
                     .context("Failed to get current directory")?;
                 current_dir.join("overcode.toml")
@@ -109,12 +298,91 @@ impl Cli {
             }
         };
 
+        let format = option_values(args_for_config, "--format")?
+            .last()
+            .map(|name| OutputFormat::from_name(name))
+            .transpose()?
+            .unwrap_or_default();
+        let jobs = option_values(args_for_config, "--jobs")?
+            .last()
+            .map(|jobs| {
+                jobs.parse::<usize>()
+                    .with_context(|| format!("--jobs expects a number, got: {}", jobs))
+            })
+            .transpose()?;
+        let output_format = option_values(args_for_config, "--output-format")?
+            .last()
+            .map(|name| StatusFormat::from_name(name))
+            .transpose()?
+            .unwrap_or_default();
+        let compare = args_for_config.iter().any(|arg| arg == "--compare");
+        let threshold = option_values(args_for_config, "--threshold")?
+            .last()
+            .map(|percent| {
+                percent.parse::<f64>()
+                    .with_context(|| format!("--threshold expects a percentage, got: {}", percent))
+            })
+            .transpose()?;
+        let baseline = option_values(args_for_config, "--baseline")?.last().cloned();
+        let fail_fast = args_for_config.iter().any(|arg| arg == "--fail-fast");
+        let include_excluded = args_for_config.iter().any(|arg| arg == "--include-excluded");
+        let refresh_images = args_for_config.iter().any(|arg| arg == "--refresh-images");
+        let no_init = args_for_config.iter().any(|arg| arg == "--no-init");
+        let no_podman = args_for_config.iter().any(|arg| arg == "--no-podman");
+        let no_gitignore = args_for_config.iter().any(|arg| arg == "--no-gitignore");
+        let check_command = args_for_config.iter().any(|arg| arg == "--check-command");
+        let gc_volumes = args_for_config.iter().any(|arg| arg == "--volumes");
+        let gc_keep_days = option_values(args_for_config, "--keep-days")?
+            .last()
+            .map(|days| {
+                days.parse::<u32>()
+                    .with_context(|| format!("--keep-days expects a number of days, got: {}", days))
+            })
+            .transpose()?;
+        let gc_keep_count = option_values(args_for_config, "--keep-count")?
+            .last()
+            .map(|count| {
+                count.parse::<usize>()
+                    .with_context(|| format!("--keep-count expects a number, got: {}", count))
+            })
+            .transpose()?;
+        let dry_run = args_for_config.iter().any(|arg| arg == "--dry-run");
+        let tags = option_values(args_for_config, "--tag")?;
+        let deadline = option_values(args_for_config, "--deadline")?
+            .last()
+            .map(|secs| {
+                secs.parse::<u64>()
+                    .map(Duration::from_secs)
+                    .with_context(|| format!("--deadline expects a number of seconds, got: {}", secs))
+            })
+            .transpose()?;
+        let timeout = option_values(args_for_config, "--timeout")?
+            .last()
+            .map(|secs| {
+                secs.parse::<u64>()
+                    .map(Duration::from_secs)
+                    .with_context(|| format!("--timeout expects a number of seconds, got: {}", secs))
+            })
+            .transpose()?;
+        let run_name = if matches!(command, Command::Run) {
+            positional_args(&args_for_config[2..])
+                .into_iter()
+                .nth(usize::from(positional_config.is_some()))
+        } else {
+            None
+        };
+        let history = if matches!(command, Command::Run) {
+            history_count(args_for_config)
+        } else {
+            None
+        };
+
         let root_dir = config_path
             .parent()
             .map(|p| p.to_path_buf())
             .ok_or_else(|| anyhow::anyhow!("Config file has no parent directory"))?;
 
-        Ok(Self { command, root_dir, config_path, extra_args })
+        Ok(Self { command, root_dir, config_path, extra_args, no_gitignore, log_level, check_command, run_name, gc_volumes, gc_keep_days, gc_keep_count, dry_run, tags, deadline, recursive, fail_fast, no_init, no_podman, format, jobs, compare, threshold, baseline, history, output_format, timeout, include_excluded, refresh_images })
     }
 }
 
@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::fs;
 use std::io::Write;
-use log::info;
+use log::{info, warn};
+use crate::atomic_file::atomic_write;
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
@@ -11,28 +14,112 @@ pub struct Config {
     pub driver_patterns: Vec<MappingEntry>,
     #[serde(default)]
     pub mock_patterns: Vec<MappingEntry>,
+    #[serde(default)]
+    pub bench_patterns: Vec<MappingEntry>,
+    #[serde(default)]
+    pub test_ignores: Vec<String>,
+    #[serde(default)]
+    pub git_tracked_only: bool,
+    pub secret_env_pattern: Option<String>,
+    pub testcase_template: Option<String>,
     pub command: Option<CommandConfig>,
+    pub podman: Option<PodmanConfig>,
+    pub run_history: Option<RunHistoryConfig>,
+    pub run_test: Option<RunTestConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PodmanConfig {
+    pub socket: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub log_level: Option<String>,
+    pub max_parallel: Option<usize>,
+    pub pull_concurrency: Option<usize>,
+    pub auto_start_machine: Option<bool>,
+    pub machine_start_timeout_secs: Option<u64>,
+    pub api: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RunHistoryConfig {
+    pub max_entries: Option<usize>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MappingEntry {
-    pub pattern: String,
+    #[serde(default)]
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub glob: Option<String>,
     #[serde(rename = "testcase")]
     pub testcase: String,
     #[serde(default)]
     pub mount_path: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub env_file_pattern: Option<String>,
+    #[serde(default)]
+    pub match_dirs: bool,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct CommandConfig {
     pub test: Option<RunTestConfig>,
     pub run: Option<RunTestConfig>,
+    pub bench: Option<RunTestConfig>,
+    #[serde(default)]
+    pub custom: HashMap<String, RunTestConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaceTarget {
+    #[default]
+    DriverFile,
+    Testcase,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContainerMode {
+    #[default]
+    PerTest,
+    Shared,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ReplaceRule {
     pub pattern: String,
     pub replace: String,
+    #[serde(default)]
+    pub replace_target: ReplaceTarget,
+    #[serde(default)]
+    pub global: bool,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VolumeMount {
+    pub name: String,
+    pub container_path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactWhen {
+    #[default]
+    Success,
+    Always,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Artifact {
+    pub container_path: String,
+    pub host_path: String,
+    #[serde(default)]
+    pub when: ArtifactWhen,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -43,6 +130,158 @@ pub struct RunTestConfig {
     pub image: Option<String>,
     #[serde(default)]
     pub replace_rule: Vec<ReplaceRule>,
+    #[serde(default)]
+    pub run_args_file: Option<String>,
+    #[serde(default = "default_true")]
+    pub touch_mocks: bool,
+    #[serde(default)]
+    pub allow_mock_conflicts: bool,
+    #[serde(default)]
+    pub require_testcase: bool,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeMount>,
+    #[serde(default)]
+    pub artifacts: Vec<Artifact>,
+    #[serde(default = "default_true")]
+    pub inject_env: bool,
+    #[serde(default)]
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub container_mode: ContainerMode,
+}
+
+impl RunTestConfig {
+    fn path_fields_mut(&mut self) -> Vec<&mut String> {
+        let mut fields: Vec<&mut String> = self.env_file.iter_mut().chain(self.run_args_file.iter_mut()).collect();
+        fields.extend(self.artifacts.iter_mut().map(|artifact| &mut artifact.host_path));
+        fields
+    }
+}
+
+fn expand_path_with(value: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String> {
+    let lookup_var = |name: &str| {
+        lookup(name).ok_or_else(|| anyhow::anyhow!("Environment variable {} is not set (in {:?})", name, value))
+    };
+
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    if rest == "~" || rest.starts_with("~/") {
+        expanded.push_str(&lookup_var("HOME")?);
+        rest = &rest[1..];
+    }
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, remainder) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed ${{ in {:?}", value))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if name.is_empty() {
+            expanded.push('$');
+        } else {
+            expanded.push_str(&lookup_var(name)?);
+        }
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn expand_path(value: &str) -> Result<String> {
+    expand_path_with(value, &|name| std::env::var(name).ok())
+}
+
+#[derive(Debug)]
+pub struct InvalidConfig(pub String);
+
+impl fmt::Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidConfig {}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidationContext<'a> {
+    pub no_podman: bool,
+    pub root_dir: Option<&'a Path>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+fn merge_command_defaults(target: &mut toml::Value, defaults: &toml::Table) {
+    let Some(target) = target.as_table_mut() else {
+        return;
+    };
+    for (key, value) in defaults {
+        target.entry(key.as_str()).or_insert_with(|| value.clone());
+    }
+}
+
+fn apply_command_defaults(table: &mut toml::Table) -> Result<()> {
+    let Some(command) = table.get_mut("command").and_then(toml::Value::as_table_mut) else {
+        return Ok(());
+    };
+    let Some(defaults) = command.remove("defaults") else {
+        return Ok(());
+    };
+    let toml::Value::Table(defaults) = defaults else {
+        anyhow::bail!("[command.defaults] must be a table");
+    };
+
+    for name in ["test", "run", "bench"] {
+        if let Some(target) = command.get_mut(name) {
+            merge_command_defaults(target, &defaults);
+        }
+    }
+    if let Some(custom) = command.get_mut("custom").and_then(toml::Value::as_table_mut) {
+        for (_, target) in custom.iter_mut() {
+            merge_command_defaults(target, &defaults);
+        }
+    }
+    if let Some(target) = table.get_mut("run_test") {
+        merge_command_defaults(target, &defaults);
+    }
+    Ok(())
+}
+
+impl MappingEntry {
+    pub fn source(&self) -> &str {
+        self.glob.as_deref().or(self.pattern.as_deref()).unwrap_or_default()
+    }
+
+    fn validate(&self, section: &str) -> Result<()> {
+        match (&self.pattern, &self.glob) {
+            (Some(pattern), Some(glob)) => anyhow::bail!(
+                "pattern and glob are mutually exclusive in [[{}]] (pattern = {:?}, glob = {:?})",
+                section, pattern, glob
+            ),
+            (None, None) => anyhow::bail!(
+                "Either pattern or glob is required in [[{}]] (testcase = {:?})",
+                section, self.testcase
+            ),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Config {
@@ -50,12 +289,137 @@ impl Config {
         let content = fs::read_to_string(config_path)
             .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
         
-        let config: Config = toml::from_str(&content)
+        let mut config = Self::parse(strip_bom(&content))
             .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
+        config.validate_mappings()
+            .with_context(|| format!("Invalid config file: {:?}", config_path))?;
+        
+        if config.run_test.is_some() {
+            if config.migrate_run_test() {
+                warn!(
+                    "[run_test] は非推奨です。[command.test] に移行してください（'overcode migrate-config' で自動的に移行できます）: {:?}",
+                    config_path
+                );
+            } else {
+                warn!("[command.test] があるため [run_test] は無視されます: {:?}", config_path);
+            }
+        }
+        config.expand_paths()
+            .with_context(|| format!("Invalid config file: {:?}", config_path))?;
         
         Ok(config)
     }
 
+    fn parse(content: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        apply_command_defaults(&mut table)?;
+        Ok(table.try_into()?)
+    }
+
+    pub fn validate(&self, context: &ValidationContext<'_>) -> Result<()> {
+        let Some(command) = self.command.as_ref() else {
+            return Ok(());
+        };
+        let sections = [("test", &command.test), ("run", &command.run)];
+        if !context.no_podman {
+            for (name, section) in sections {
+                if section.as_ref().is_some_and(|section| section.image.is_none()) {
+                    return Err(InvalidConfig(format!("command.{}.image is required unless --no-podman is used", name)).into());
+                }
+            }
+        }
+        if let Some(root_dir) = context.root_dir {
+            let sections = [&command.test, &command.run, &command.bench]
+                .into_iter()
+                .flatten()
+                .chain(command.custom.values());
+            for section in sections {
+                crate::podman_mount::resolve_env_file(section.env_file.as_deref(), root_dir)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn expand_paths(&mut self) -> Result<()> {
+        let Some(command) = self.command.as_mut() else {
+            return Ok(());
+        };
+        let sections = [&mut command.test, &mut command.run, &mut command.bench]
+            .into_iter()
+            .flatten()
+            .chain(command.custom.values_mut());
+        for section in sections {
+            for field in section.path_fields_mut() {
+                *field = expand_path(field)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_mappings(&self) -> Result<()> {
+        let sections = [
+            ("driver_patterns", &self.driver_patterns),
+            ("mock_patterns", &self.mock_patterns),
+            ("bench_patterns", &self.bench_patterns),
+        ];
+        for (section, mappings) in sections {
+            for mapping in mappings {
+                mapping.validate(section)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn migrate_run_test(&mut self) -> bool {
+        let Some(run_test) = self.run_test.take() else {
+            return false;
+        };
+
+        let command = self.command.get_or_insert_with(CommandConfig::default);
+        if command.test.is_some() {
+            return false;
+        }
+        command.test = Some(run_test);
+        true
+    }
+
+    pub fn migrate_config_file(config_path: &Path) -> Result<()> {
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config file: {:?}", config_path))?;
+        let mut document: toml_edit::DocumentMut = strip_bom(&content)
+            .parse()
+            .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
+
+        let Some(run_test) = document.remove("run_test") else {
+            info!("移行が必要な設定はありません: {:?}", config_path);
+            return Ok(());
+        };
+
+        let command = document
+            .entry("command")
+            .or_insert_with(|| {
+                let mut command = toml_edit::Table::new();
+                command.set_implicit(true);
+                toml_edit::Item::Table(command)
+            })
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow::anyhow!("[command] must be a table in {:?}", config_path))?;
+        if command.contains_key("test") {
+            info!("[command.test] があるため [run_test] を削除します: {:?}", config_path);
+        } else {
+            info!("[run_test] を [command.test] に移行します: {:?}", config_path);
+            command.insert("test", run_test);
+        }
+
+        let migrated = document.to_string();
+        Self::parse(&migrated)
+            .with_context(|| format!("Migrated config is invalid: {:?}", config_path))?;
+        let target = fs::canonicalize(config_path).unwrap_or_else(|_| config_path.to_path_buf());
+        atomic_write(&target, migrated)
+            .with_context(|| format!("Failed to write config file: {:?}", config_path))?;
+        Ok(())
+    }
+
     fn get_template_content() -> &'static str {
         r#"# overcode.toml
 [[driver_patterns]]
@@ -102,5 +466,61 @@ args = ["run", "--manifest-path", "Cargo.toml"]"#
         info!("設定ファイルを作成しました: {:?}", config_path);
         Ok(())
     }
+
+    pub fn init_gitignore(root_dir: &Path) -> Result<()> {
+        let gitignore_path = root_dir.join(".gitignore");
+        let entry = "/.overcode/";
+
+        if !gitignore_path.exists() {
+            info!(".gitignoreを作成します: {:?}", gitignore_path);
+            fs::write(&gitignore_path, format!("{}\n", entry))
+                .with_context(|| format!("Failed to create .gitignore: {:?}", gitignore_path))?;
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&gitignore_path)
+            .with_context(|| format!("Failed to read .gitignore: {:?}", gitignore_path))?;
+
+        let already_listed = content
+            .lines()
+            .map(|line| line.trim().trim_start_matches('/').trim_end_matches('/'))
+            .any(|line| line == ".overcode");
+
+        if already_listed {
+            info!(".overcodeは既に.gitignoreに含まれています: {:?}", gitignore_path);
+            return Ok(());
+        }
+
+        info!(".gitignoreに{}を追加します: {:?}", entry, gitignore_path);
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&gitignore_path)
+            .with_context(|| format!("Failed to open .gitignore: {:?}", gitignore_path))?;
+
+        let separator = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+        file.write_all(format!("{}{}\n", separator, entry).as_bytes())
+            .with_context(|| format!("Failed to write .gitignore: {:?}", gitignore_path))?;
+
+        Ok(())
+    }
 }
 
+#[cfg(test)]
+#[path = "config/driver/defaults/defaults.rs"]
+mod driver_defaults_defaults;
+
+#[cfg(test)]
+#[path = "config/driver/encoding/encoding.rs"]
+mod driver_encoding_encoding;
+
+#[cfg(test)]
+#[path = "config/driver/expand/expand.rs"]
+mod driver_expand_expand;
+
+#[cfg(test)]
+#[path = "config/driver/migrate/migrate.rs"]
+mod driver_migrate_migrate;
+
+#[cfg(test)]
+#[path = "config/driver/validate/validate.rs"]
+mod driver_validate_validate;
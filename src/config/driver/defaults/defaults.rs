@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::Config;
+
+    fn load(content: &str) -> anyhow::Result<Config> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, content).unwrap();
+        Config::load(&config_path)
+    }
+
+    #[test]
+    fn test_command_test_inherits_default_image() {
+        let config = load(r#"
+[command.defaults]
+image = "rust:1.80"
+inject_env = false
+
+[command.test]
+command = "cargo"
+args = ["test"]
+"#).unwrap();
+
+        let test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        assert_eq!(test.image.as_deref(), Some("rust:1.80"));
+        assert!(!test.inject_env);
+    }
+
+    #[test]
+    fn test_command_specific_values_win_over_defaults() {
+        let config = load(r#"
+[command.defaults]
+image = "rust:1.80"
+command = "cargo"
+args = ["build"]
+
+[command.run]
+image = "debian:12"
+args = ["run"]
+
+[command.custom.lint]
+args = ["clippy"]
+"#).unwrap();
+
+        let command = config.command.as_ref().unwrap();
+        let run = command.run.as_ref().unwrap();
+        assert_eq!(run.image.as_deref(), Some("debian:12"));
+        assert_eq!(run.command, "cargo");
+        assert_eq!(run.args, vec!["run"]);
+        let lint = &command.custom["lint"];
+        assert_eq!(lint.image.as_deref(), Some("rust:1.80"));
+        assert_eq!(lint.args, vec!["clippy"]);
+        assert!(command.test.is_none());
+    }
+
+    #[test]
+    fn test_command_defaults_must_be_table() {
+        let error = load(r#"
+[command]
+defaults = "rust:1.80"
+"#).unwrap_err();
+
+        assert!(format!("{:#}", error).contains("[command.defaults] must be a table"));
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{strip_bom, Config};
+
+    const CRLF_CONFIG: &str = "[[driver_patterns]]\r\npattern = \"src/(.+)/driver/(.+)\\\\.rs\"\r\ntestcase = \"$2\"\r\n\r\n[run_test]\r\ncommand = \"cargo\"\r\nargs = [\"test\"]\r\n";
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(strip_bom("\u{feff}command = 1"), "command = 1");
+        assert_eq!(strip_bom("command = 1"), "command = 1");
+    }
+
+    #[test]
+    fn test_load_config_with_bom_and_crlf() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, format!("\u{feff}{}", CRLF_CONFIG)).unwrap();
+
+        let config = Config::load(&config_path).unwrap();
+
+        assert_eq!(config.driver_patterns[0].pattern.as_deref(), Some("src/(.+)/driver/(.+)\\.rs"));
+        assert_eq!(config.command.unwrap().test.unwrap().args, vec!["test"]);
+    }
+
+    #[test]
+    fn test_migrate_config_file_with_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, format!("\u{feff}{}", CRLF_CONFIG)).unwrap();
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(!content.starts_with('\u{feff}'));
+        assert!(!content.contains("[run_test]"));
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{expand_path_with, Config};
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "HOME" => Some("/home/dev".to_string()),
+            "PROJECT" => Some("api".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_path_home_and_variables() {
+        assert_eq!(expand_path_with("~/foo", &lookup).unwrap(), "/home/dev/foo");
+        assert_eq!(expand_path_with("~", &lookup).unwrap(), "/home/dev");
+        assert_eq!(expand_path_with("$HOME/bar", &lookup).unwrap(), "/home/dev/bar");
+        assert_eq!(expand_path_with("${PROJECT}_env/$PROJECT.env", &lookup).unwrap(), "api_env/api.env");
+        assert_eq!(expand_path_with("~user/foo", &lookup).unwrap(), "~user/foo");
+        assert_eq!(expand_path_with("cost$/x", &lookup).unwrap(), "cost$/x");
+    }
+
+    #[test]
+    fn test_expand_path_reports_missing_variables() {
+        let error_msg = expand_path_with("$MISSING/x", &lookup).unwrap_err().to_string();
+        assert!(error_msg.contains("Environment variable MISSING is not set"));
+
+        assert!(expand_path_with("${PROJECT/x", &lookup).is_err());
+    }
+
+    #[test]
+    fn test_load_expands_path_fields() {
+        let home = std::env::var("HOME").unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+env_file = "~/foo"
+artifacts = [{ container_path = "$HOME/coverage", host_path = "$HOME/reports" }]
+
+[command.run]
+command = "cargo"
+args = ["run", "$HOME"]
+run_args_file = "$HOME/bar"
+"#).unwrap();
+
+        let config = Config::load(&config_path).unwrap();
+
+        let command = config.command.unwrap();
+        let test = command.test.unwrap();
+        let run = command.run.unwrap();
+        assert_eq!(test.env_file, Some(format!("{}/foo", home)));
+        assert_eq!(test.artifacts[0].host_path, format!("{}/reports", home));
+        assert_eq!(test.artifacts[0].container_path, "$HOME/coverage");
+        assert_eq!(run.run_args_file, Some(format!("{}/bar", home)));
+        assert_eq!(run.args, vec!["run", "$HOME"]);
+    }
+}
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+    use crate::config::Config;
+
+    const OLD_ONLY: &str = r#"
+[run_test]
+command = "cargo"
+args = ["test", "old"]
+"#;
+
+    const NEW_ONLY: &str = r#"
+[command.test]
+command = "cargo"
+args = ["test", "new"]
+"#;
+
+    const BOTH: &str = r#"
+[run_test]
+command = "cargo"
+args = ["test", "old"]
+
+[command.test]
+command = "cargo"
+args = ["test", "new"]
+"#;
+
+    fn write_config(temp_dir: &TempDir, content: &str) -> PathBuf {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, content).unwrap();
+        config_path
+    }
+
+    fn test_args(config: &Config) -> Vec<String> {
+        config.command.as_ref().unwrap().test.as_ref().unwrap().args.clone()
+    }
+
+    #[test]
+    fn test_load_migrates_old_run_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::load(&write_config(&temp_dir, OLD_ONLY)).unwrap();
+
+        assert_eq!(test_args(&config), vec!["test", "old"]);
+        assert!(config.run_test.is_none());
+    }
+
+    #[test]
+    fn test_load_keeps_new_command_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::load(&write_config(&temp_dir, NEW_ONLY)).unwrap();
+
+        assert!(!config.migrate_run_test());
+        assert_eq!(test_args(&config), vec!["test", "new"]);
+    }
+
+    #[test]
+    fn test_load_prefers_command_test_over_run_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::load(&write_config(&temp_dir, BOTH)).unwrap();
+
+        assert_eq!(test_args(&config), vec!["test", "new"]);
+        assert!(config.run_test.is_none());
+    }
+
+    #[test]
+    fn test_migrate_config_file_moves_run_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_config(&temp_dir, OLD_ONLY);
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(!content.contains("[run_test]"));
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(test_args(&config), vec!["test", "old"]);
+    }
+
+    #[test]
+    fn test_migrate_config_file_drops_run_test_when_both_present() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_config(&temp_dir, BOTH);
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(!content.contains("[run_test]"));
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(test_args(&config), vec!["test", "new"]);
+    }
+
+    #[test]
+    fn test_migrate_config_file_leaves_new_config_untouched() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_config(&temp_dir, NEW_ONLY);
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), NEW_ONLY);
+    }
+
+    #[test]
+    fn test_migrate_config_file_keeps_comments_and_other_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_config(&temp_dir, r#"# project settings
+[[driver_patterns]]
+pattern = "tests/(.+)\\.sh"  # shell drivers
+testcase = "$1"
+
+# legacy test command
+[run_test]
+command = "cargo"
+args = ["test", "old"]  # keep in sync with CI
+
+[command.run]
+command = "cargo"
+args = ["run"]
+"#);
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("# project settings\n"));
+        assert!(content.contains("pattern = \"tests/(.+)\\\\.sh\"  # shell drivers\n"));
+        assert!(content.contains("# legacy test command\n[command.test]\n"));
+        assert!(content.contains("args = [\"test\", \"old\"]  # keep in sync with CI\n"));
+        let config: Config = toml::from_str(&content).unwrap();
+        assert_eq!(test_args(&config), vec!["test", "old"]);
+        assert_eq!(config.command.unwrap().run.unwrap().args, vec!["run"]);
+    }
+
+    #[test]
+    fn test_migrate_config_file_leaves_no_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = write_config(&temp_dir, OLD_ONLY);
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        let entries: Vec<_> = fs::read_dir(temp_dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(entries, vec!["overcode.toml"]);
+    }
+
+    #[test]
+    fn test_migrate_config_file_writes_through_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("shared.toml");
+        fs::write(&target, OLD_ONLY).unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        std::os::unix::fs::symlink(&target, &config_path).unwrap();
+
+        Config::migrate_config_file(&config_path).unwrap();
+
+        assert!(config_path.is_symlink());
+        assert!(fs::read_to_string(&target).unwrap().contains("[command.test]"));
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{Config, InvalidConfig, ValidationContext};
+
+    fn load(content: &str) -> Config {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, content).unwrap();
+        Config::load(&config_path).unwrap()
+    }
+
+    #[test]
+    fn test_missing_test_image_is_rejected() {
+        let config = load(r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+"#);
+
+        let err = config.validate(&ValidationContext::default()).unwrap_err();
+
+        assert!(err.downcast_ref::<InvalidConfig>().is_some());
+        assert_eq!(err.to_string(), "command.test.image is required unless --no-podman is used");
+    }
+
+    #[test]
+    fn test_missing_run_image_is_rejected() {
+        let config = load(r#"
+[command.test]
+image = "rust:1.80"
+command = "cargo"
+args = ["test"]
+
+[command.run]
+command = "cargo"
+args = ["run"]
+"#);
+
+        let err = config.validate(&ValidationContext::default()).unwrap_err();
+
+        assert_eq!(err.to_string(), "command.run.image is required unless --no-podman is used");
+    }
+
+    #[test]
+    fn test_no_podman_allows_missing_images() {
+        let config = load(r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+
+[command.run]
+command = "cargo"
+args = ["run"]
+"#);
+
+        assert!(config.validate(&ValidationContext { no_podman: true, ..Default::default() }).is_ok());
+    }
+
+    #[test]
+    fn test_default_image_satisfies_validation() {
+        let config = load(r#"
+[command.defaults]
+image = "rust:1.80"
+
+[command.test]
+command = "cargo"
+args = ["test"]
+"#);
+
+        assert!(config.validate(&ValidationContext::default()).is_ok());
+    }
+
+    #[test]
+    fn test_missing_env_file_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(r#"
+[command.test]
+image = "rust:1.80"
+command = "cargo"
+args = ["test"]
+env_file = "secrets/test.env"
+"#);
+
+        let err = config
+            .validate(&ValidationContext { root_dir: Some(temp_dir.path()), ..Default::default() })
+            .unwrap_err();
+
+        assert!(err.downcast_ref::<InvalidConfig>().is_some());
+        assert!(err.to_string().contains("env_file not found"));
+        assert!(err.to_string().contains("secrets/test.env"));
+    }
+
+    #[test]
+    fn test_existing_env_file_passes_validation() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".env"), "TOKEN=abc\n").unwrap();
+        let config = load(r#"
+[command.run]
+image = "rust:1.80"
+command = "cargo"
+args = ["run"]
+env_file = ".env"
+"#);
+
+        assert!(config.validate(&ValidationContext { root_dir: Some(temp_dir.path()), ..Default::default() }).is_ok());
+    }
+
+    #[test]
+    fn test_no_podman_still_checks_env_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+env_file = "missing.env"
+"#);
+
+        let err = config
+            .validate(&ValidationContext { no_podman: true, root_dir: Some(temp_dir.path()) })
+            .unwrap_err();
+
+        assert!(err.to_string().contains("env_file not found"));
+    }
+
+    #[test]
+    fn test_bench_and_custom_env_files_are_checked() {
+        let temp_dir = TempDir::new().unwrap();
+        for (section, env_file) in [("command.bench", "bench.env"), ("command.custom.seed", "seed.env")] {
+            let config = load(&format!(r#"
+[{}]
+image = "rust:1.80"
+command = "cargo"
+args = ["run"]
+env_file = "{}"
+"#, section, env_file));
+
+            let err = config
+                .validate(&ValidationContext { root_dir: Some(temp_dir.path()), ..Default::default() })
+                .unwrap_err();
+
+            assert!(err.to_string().contains(env_file), "{}: {}", section, err);
+        }
+    }
+}
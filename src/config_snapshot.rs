@@ -0,0 +1,78 @@
+use anyhow::Context;
+use regex::Regex;
+use crate::config::{ContainerMode, PodmanConfig, ReplaceTarget, RunTestConfig};
+
+pub const DEFAULT_SECRET_PATTERN: &str = "(?i)(token|secret|password|key)";
+const REDACTED: &str = "***";
+
+pub fn compile_secret_pattern(pattern: Option<&str>) -> anyhow::Result<Regex> {
+    let pattern = pattern.unwrap_or(DEFAULT_SECRET_PATTERN);
+    Regex::new(pattern).with_context(|| format!("Invalid secret_env_pattern: {}", pattern))
+}
+
+pub fn redact_env(vars: &[(String, String)], secret_pattern: &Regex) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(key, value)| {
+            let value = if secret_pattern.is_match(key) { REDACTED.to_string() } else { value.clone() };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| value.to_string())
+}
+
+pub fn format_snapshot(
+    section: &str,
+    run_test: &RunTestConfig,
+    podman: Option<&PodmanConfig>,
+    env: &[(String, String)],
+    secret_pattern: &Regex,
+) -> String {
+    let mut lines = vec![
+        format!("section: {}", section),
+        format!("image: {}", optional(run_test.image.as_deref())),
+        format!("command: {}", run_test.command),
+        format!("args: {:?}", run_test.args),
+        format!("container_mode: {}", match run_test.container_mode {
+            ContainerMode::PerTest => "per-test",
+            ContainerMode::Shared => "shared",
+        }),
+    ];
+    for rule in &run_test.replace_rule {
+        lines.push(format!(
+            "replace_rule: {:?} -> {:?} (target: {}, global: {}, case_insensitive: {})",
+            rule.pattern,
+            rule.replace,
+            match rule.replace_target {
+                ReplaceTarget::DriverFile => "driver_file",
+                ReplaceTarget::Testcase => "testcase",
+            },
+            rule.global,
+            rule.case_insensitive
+        ));
+    }
+    if let Some(podman) = podman {
+        lines.push(format!(
+            "podman: socket={} timeout_secs={} log_level={} max_parallel={} pull_concurrency={}",
+            optional(podman.socket.as_deref()),
+            optional(podman.timeout_secs),
+            optional(podman.log_level.as_deref()),
+            optional(podman.max_parallel),
+            optional(podman.pull_concurrency)
+        ));
+    }
+    if let Some(ref env_file) = run_test.env_file {
+        lines.push(format!("env_file: {}", env_file));
+    }
+    for (key, value) in redact_env(env, secret_pattern) {
+        lines.push(format!("env: {}={}", key, value));
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+#[path = "config_snapshot/driver/redact/redact.rs"]
+mod driver_redact_redact;
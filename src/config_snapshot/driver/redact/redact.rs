@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::config_snapshot::{compile_secret_pattern, format_snapshot, redact_env};
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_redact_env_with_default_pattern() {
+        let pattern = compile_secret_pattern(None).unwrap();
+
+        let redacted = redact_env(
+            &vars(&[("API_TOKEN", "abc"), ("db_password", "hunter2"), ("SSH_KEY", "k"), ("SEED", "42")]),
+            &pattern,
+        );
+
+        assert_eq!(redacted, vars(&[("API_TOKEN", "***"), ("db_password", "***"), ("SSH_KEY", "***"), ("SEED", "42")]));
+    }
+
+    #[test]
+    fn test_redact_env_with_custom_pattern() {
+        let pattern = compile_secret_pattern(Some("^PRIVATE_")).unwrap();
+
+        let redacted = redact_env(&vars(&[("PRIVATE_URL", "x"), ("API_TOKEN", "abc")]), &pattern);
+
+        assert_eq!(redacted, vars(&[("PRIVATE_URL", "***"), ("API_TOKEN", "abc")]));
+    }
+
+    #[test]
+    fn test_compile_secret_pattern_invalid() {
+        let error_msg = compile_secret_pattern(Some("(")).unwrap_err().to_string();
+
+        assert!(error_msg.contains("Invalid secret_env_pattern: ("));
+    }
+
+    #[test]
+    fn test_format_snapshot_lists_effective_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test", "{testcase}"]
+env_file = "ci.env"
+replace_rule = [{ pattern = "^src/", replace = "/work/src/", global = true }]
+
+[podman]
+socket = "unix:///run/podman.sock"
+max_parallel = 4
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+
+        let snapshot = format_snapshot(
+            "command.test",
+            run_test,
+            config.podman.as_ref(),
+            &vars(&[("GITHUB_TOKEN", "ghp_secret"), ("RUST_LOG", "debug")]),
+            &compile_secret_pattern(None).unwrap(),
+        );
+
+        assert_eq!(snapshot, concat!(
+            "section: command.test\n",
+            "image: docker.io/library/rust:latest\n",
+            "command: cargo\n",
+            "args: [\"test\", \"{testcase}\"]\n",
+            "container_mode: per-test\n",
+            "replace_rule: \"^src/\" -> \"/work/src/\" (target: driver_file, global: true, case_insensitive: false)\n",
+            "podman: socket=unix:///run/podman.sock timeout_secs=- log_level=- max_parallel=4 pull_concurrency=-\n",
+            "env_file: ci.env\n",
+            "env: GITHUB_TOKEN=***\n",
+            "env: RUST_LOG=debug\n",
+        ));
+        assert!(!snapshot.contains("ghp_secret"));
+    }
+}
@@ -0,0 +1,50 @@
+use anyhow::Context;
+use std::fs;
+use std::path::Path;
+
+pub fn parse_env_file(content: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+
+    for (index, line) in crate::config::strip_bom(content).lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("line {}: expected KEY=VALUE, got: {}", index + 1, line))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            anyhow::bail!("line {}: invalid variable name: {:?}", index + 1, key);
+        }
+
+        vars.push((key.to_string(), value.trim().to_string()));
+    }
+
+    Ok(vars)
+}
+
+pub fn load_env_file(path: &Path) -> anyhow::Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file: {}", path.display()))?;
+    parse_env_file(&content)
+        .with_context(|| format!("Invalid env file: {}", path.display()))
+}
+
+pub fn context_env(root_dir: &str, run_id: &str) -> Vec<(String, String)> {
+    vec![
+        ("OVERCODE_ROOT_DIR".to_string(), root_dir.to_string()),
+        ("OVERCODE_RUN_ID".to_string(), run_id.to_string()),
+    ]
+}
+
+pub fn build_env_args(vars: &[(String, String)]) -> Vec<String> {
+    vars.iter()
+        .flat_map(|(key, value)| ["-e".to_string(), format!("{}={}", key, value)])
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "env_file/driver/parse/parse.rs"]
+mod driver_parse_parse;
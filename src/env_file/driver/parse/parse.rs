@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::env_file::{build_env_args, parse_env_file};
+
+    #[test]
+    fn test_parse_env_file_skips_comments_and_blank_lines() {
+        let vars = parse_env_file("# seed for fixtures\nSEED=42\n\n  FEATURE_X = on \n").unwrap();
+
+        assert_eq!(
+            vars,
+            vec![
+                ("SEED".to_string(), "42".to_string()),
+                ("FEATURE_X".to_string(), "on".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_env_file_strips_bom_and_crlf() {
+        let vars = parse_env_file("\u{feff}DATABASE_URL=postgres://db\r\n# comment\r\nSEED=42\r\n").unwrap();
+
+        assert_eq!(vars, vec![
+            ("DATABASE_URL".to_string(), "postgres://db".to_string()),
+            ("SEED".to_string(), "42".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_env_file_keeps_equals_in_value() {
+        let vars = parse_env_file("DATABASE_URL=postgres://u:p@db/app?sslmode=disable\nEMPTY=\n").unwrap();
+
+        assert_eq!(vars[0].1, "postgres://u:p@db/app?sslmode=disable");
+        assert_eq!(vars[1], ("EMPTY".to_string(), String::new()));
+    }
+
+    #[test]
+    fn test_parse_env_file_reports_line_number_for_missing_equals() {
+        let error = parse_env_file("SEED=42\n# comment\nFEATURE_X\n").unwrap_err();
+
+        assert!(error.to_string().contains("line 3"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_env_file_rejects_invalid_key() {
+        assert!(parse_env_file("=value\n").is_err());
+        assert!(parse_env_file("MY KEY=value\n").is_err());
+    }
+
+    #[test]
+    fn test_build_env_args_pairs_flags() {
+        let args = build_env_args(&[
+            ("SEED".to_string(), "42".to_string()),
+            ("FEATURE_X".to_string(), "on".to_string()),
+        ]);
+
+        assert_eq!(args, vec!["-e", "SEED=42", "-e", "FEATURE_X=on"]);
+    }
+}
@@ -0,0 +1,67 @@
+const FALLBACK_LINES: usize = 30;
+const MAX_EXCERPT_LINES: usize = 40;
+
+fn is_rust_panic(line: &str) -> bool {
+    line.starts_with("thread '") && line.contains("panicked")
+}
+
+fn is_pytest_section_header(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("___") && line.ends_with("___") && line.contains(' ')
+}
+
+fn block_from<'a>(lines: &[&'a str], start: usize, is_end: impl Fn(&str) -> bool) -> Vec<&'a str> {
+    let mut block = vec![lines[start]];
+    block.extend(
+        lines[start + 1..]
+            .iter()
+            .take_while(|line| !is_end(line))
+            .take(MAX_EXCERPT_LINES - 1),
+    );
+    block
+}
+
+fn rust_panic_block<'a>(lines: &[&'a str]) -> Option<Vec<&'a str>> {
+    let start = lines
+        .iter()
+        .rposition(|line| is_rust_panic(line))
+        .or_else(|| lines.iter().rposition(|line| line.contains("assertion failed")))?;
+
+    Some(block_from(lines, start, |line| {
+        line.trim().is_empty() || line.starts_with("stack backtrace:")
+    }))
+}
+
+fn pytest_failure_block<'a>(lines: &[&'a str]) -> Option<Vec<&'a str>> {
+    let start = lines.iter().rposition(|line| is_pytest_section_header(line))?;
+
+    Some(block_from(lines, start, |line| {
+        is_pytest_section_header(line) || line.starts_with("===")
+    }))
+}
+
+fn python_traceback_block<'a>(lines: &[&'a str]) -> Option<Vec<&'a str>> {
+    let start = lines
+        .iter()
+        .rposition(|line| line.starts_with("Traceback (most recent call last):"))?;
+
+    Some(block_from(lines, start, |line| line.trim().is_empty()))
+}
+
+fn tail<'a>(lines: &[&'a str], count: usize) -> Vec<&'a str> {
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+pub fn extract_excerpt(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().collect();
+
+    rust_panic_block(&lines)
+        .or_else(|| pytest_failure_block(&lines))
+        .or_else(|| python_traceback_block(&lines))
+        .unwrap_or_else(|| tail(&lines, FALLBACK_LINES))
+        .join("\n")
+}
+
+#[cfg(test)]
+#[path = "failure_excerpt/driver/samples/samples.rs"]
+mod driver_samples_samples;
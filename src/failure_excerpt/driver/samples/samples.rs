@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::failure_excerpt::extract_excerpt;
+
+    const RUST_PANIC: &str = "\
+running 3 tests
+test tests::ok_one ... ok
+test tests::broken ... FAILED
+test tests::ok_two ... ok
+
+failures:
+
+---- tests::broken stdout ----
+
+thread 'tests::broken' panicked at src/lib.rs:10:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::broken
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+    const RUST_TWO_PANICS: &str = "\
+thread 'tests::first' panicked at src/lib.rs:3:5:
+first failure
+
+thread 'tests::second' panicked at src/lib.rs:7:5:
+assertion failed: value.is_some()
+stack backtrace:
+   0: rust_begin_unwind
+";
+
+    const RUST_ASSERTION_ONLY: &str = "\
+compiling...
+assertion failed: result.is_ok()
+  at src/main.rs:4
+
+done
+";
+
+    const PYTEST_FAILURE: &str = "\
+============================= test session starts ==============================
+collected 2 items
+
+tests/test_math.py .F                                                    [100%]
+
+=================================== FAILURES ===================================
+_________________________________ test_divide __________________________________
+
+    def test_divide():
+>       assert divide(4, 2) == 3
+E       assert 2.0 == 3
+
+tests/test_math.py:8: AssertionError
+=========================== short test summary info ============================
+FAILED tests/test_math.py::test_divide - assert 2.0 == 3
+========================= 1 failed, 1 passed in 0.02s ==========================
+";
+
+    const PYTHON_TRACEBACK: &str = "\
+starting
+Traceback (most recent call last):
+  File \"main.py\", line 3, in <module>
+    run()
+ZeroDivisionError: division by zero
+
+exiting
+";
+
+    #[test]
+    fn test_extract_excerpt_table() {
+        let cases: &[(&str, &str, &str)] = &[
+            (
+                "rust panic block",
+                RUST_PANIC,
+                "thread 'tests::broken' panicked at src/lib.rs:10:9:\n\
+                 assertion `left == right` failed\n  left: 1\n right: 2\n\
+                 note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace",
+            ),
+            (
+                "last of several rust panics, stops at backtrace",
+                RUST_TWO_PANICS,
+                "thread 'tests::second' panicked at src/lib.rs:7:5:\n\
+                 assertion failed: value.is_some()",
+            ),
+            (
+                "rust assertion without thread line",
+                RUST_ASSERTION_ONLY,
+                "assertion failed: result.is_ok()\n  at src/main.rs:4",
+            ),
+            (
+                "pytest failure section",
+                PYTEST_FAILURE,
+                "_________________________________ test_divide __________________________________\n\
+                 \n    def test_divide():\n>       assert divide(4, 2) == 3\n\
+                 E       assert 2.0 == 3\n\ntests/test_math.py:8: AssertionError",
+            ),
+            (
+                "python traceback",
+                PYTHON_TRACEBACK,
+                "Traceback (most recent call last):\n  File \"main.py\", line 3, in <module>\n\
+                 \x20   run()\nZeroDivisionError: division by zero",
+            ),
+        ];
+
+        for (name, output, expected) in cases {
+            assert_eq!(extract_excerpt(output), *expected, "case: {}", name);
+        }
+    }
+
+    #[test]
+    fn test_extract_excerpt_falls_back_to_last_30_lines() {
+        let output: String = (1..=50).map(|i| format!("line {}\n", i)).collect();
+        
+        let excerpt = extract_excerpt(&output);
+        let lines: Vec<&str> = excerpt.lines().collect();
+        
+        assert_eq!(lines.len(), 30);
+        assert_eq!(lines[0], "line 21");
+        assert_eq!(lines[29], "line 50");
+    }
+
+    #[test]
+    fn test_extract_excerpt_short_output_fallback() {
+        assert_eq!(extract_excerpt("only line\n"), "only line");
+        assert_eq!(extract_excerpt(""), "");
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+use log::{debug, info, warn};
+use crate::config::Config;
+use crate::podman_mount;
+use crate::test::{self, DriverIndex};
+
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+const STATE_DIR: &str = ".overcode";
+
+#[derive(Debug)]
+pub enum WatchEvent {
+    Changed(Vec<PathBuf>),
+    Stop,
+}
+
+pub fn format_index(index: &DriverIndex) -> String {
+    let mut table = format!("{:<7}  {:<24}  PATH\n", "KIND", "TESTCASE");
+    for (driver_file, key) in &index.drivers {
+        table.push_str(&format!("{:<7}  {:<24}  {}\n", "driver", key.as_deref().unwrap_or("-"), driver_file));
+    }
+    for (mock_file, key, mount_path) in &index.mocks {
+        let mount = mount_path
+            .as_ref()
+            .map(|mount_path| format!(" -> {}", mount_path))
+            .unwrap_or_default();
+        table.push_str(&format!("{:<7}  {:<24}  {}{}\n", "mock", key, mock_file, mount));
+    }
+    table
+}
+
+pub fn process_index(config: &Config, root_dir: &Path) -> Result<()> {
+    let index = test::index_drivers(config, root_dir)?;
+    std::io::stdout().write_all(format_index(&index).as_bytes())
+        .context("Failed to write driver index")?;
+    info!("Indexed {} driver(s) and {} mock(s)", index.drivers.len(), index.mocks.len());
+    Ok(())
+}
+
+fn is_state_path(path: &Path, root_dir: &Path) -> bool {
+    path.strip_prefix(root_dir)
+        .is_ok_and(|relative| relative.starts_with(STATE_DIR))
+}
+
+pub fn start_watcher(root_dir: &Path, events: Sender<WatchEvent>) -> Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) if event.kind.is_access() => {}
+        Ok(event) => {
+            events.send(WatchEvent::Changed(event.paths)).ok();
+        }
+        Err(err) => warn!("File watcher error: {}", err),
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(root_dir, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", root_dir.display()))?;
+    Ok(watcher)
+}
+
+pub fn watch_loop(
+    events: &Receiver<WatchEvent>,
+    root_dir: &Path,
+    debounce: Duration,
+    on_batch: &mut dyn FnMut(&[PathBuf]) -> Result<()>,
+) -> Result<()> {
+    let relevant = |paths: Vec<PathBuf>| paths.into_iter().filter(|path| !is_state_path(path, root_dir));
+
+    loop {
+        let mut batch: Vec<PathBuf> = match events.recv() {
+            Ok(WatchEvent::Changed(paths)) => relevant(paths).collect(),
+            Ok(WatchEvent::Stop) | Err(_) => return Ok(()),
+        };
+        if batch.is_empty() {
+            continue;
+        }
+
+        loop {
+            match events.recv_timeout(debounce) {
+                Ok(WatchEvent::Changed(paths)) => batch.extend(relevant(paths)),
+                Ok(WatchEvent::Stop) => return Ok(()),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        batch.sort();
+        batch.dedup();
+        if let Err(err) = on_batch(&batch) {
+            warn!("Re-indexing failed: {:#}", err);
+        }
+    }
+}
+
+pub fn process_index_watch(config: &Config, root_dir: &Path, debounce: Duration) -> Result<()> {
+    let root_dir = podman_mount::canonical_root_dir(root_dir)?;
+    process_index(config, &root_dir)?;
+
+    let (sender, events) = mpsc::channel();
+    let stop = sender.clone();
+    if let Err(err) = ctrlc::set_handler(move || {
+        stop.send(WatchEvent::Stop).ok();
+    }) {
+        warn!("Failed to install interrupt handler for index --watch: {}", err);
+    }
+    let _watcher = start_watcher(&root_dir, sender)?;
+    info!(
+        "Watching {} for changes (debounce {}ms). Press Ctrl-C to stop",
+        root_dir.display(),
+        debounce.as_millis()
+    );
+
+    watch_loop(&events, &root_dir, debounce, &mut |paths| {
+        debug!("Re-indexing after {} changed path(s)", paths.len());
+        process_index(config, &root_dir)
+    })?;
+    info!("Stopped watching {}", root_dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "index/driver/watch/watch.rs"]
+mod driver_watch_watch;
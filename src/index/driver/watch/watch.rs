@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use crate::index::{format_index, start_watcher, watch_loop, WatchEvent};
+    use crate::test::DriverIndex;
+
+    const DEBOUNCE: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn test_format_index_lists_drivers_and_mocks() {
+        let index = DriverIndex {
+            drivers: vec![
+                ("src/a/driver/x/ok.rs".to_string(), Some("x_ok".to_string())),
+                ("src/b/driver/y.rs".to_string(), None),
+            ],
+            mocks: vec![("src/a/mock/x/ok.rs".to_string(), "x_ok".to_string(), Some("src/a/x.rs".to_string()))],
+        };
+
+        let table = format_index(&index);
+
+        assert!(table.contains("driver   x_ok                      src/a/driver/x/ok.rs\n"));
+        assert!(table.contains("driver   -                         src/b/driver/y.rs\n"));
+        assert!(table.contains("mock     x_ok                      src/a/mock/x/ok.rs -> src/a/x.rs\n"));
+    }
+
+    #[test]
+    fn test_watch_loop_debounces_changes_into_one_batch() {
+        let root_dir = PathBuf::from("/repo");
+        let (sender, events) = mpsc::channel();
+        sender.send(WatchEvent::Changed(vec![root_dir.join("src/b.rs")])).unwrap();
+        sender.send(WatchEvent::Changed(vec![root_dir.join("src/a.rs"), root_dir.join("src/b.rs")])).unwrap();
+
+        let mut batches = Vec::new();
+        watch_loop(&events, &root_dir, DEBOUNCE, &mut |paths| {
+            batches.push(paths.to_vec());
+            sender.send(WatchEvent::Stop).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(batches, vec![vec![root_dir.join("src/a.rs"), root_dir.join("src/b.rs")]]);
+    }
+
+    #[test]
+    fn test_watch_loop_ignores_state_directory() {
+        let root_dir = PathBuf::from("/repo");
+        let (sender, events) = mpsc::channel();
+        sender.send(WatchEvent::Changed(vec![root_dir.join(".overcode/logs/run.log")])).unwrap();
+        sender.send(WatchEvent::Stop).unwrap();
+
+        let mut calls = 0;
+        watch_loop(&events, &root_dir, DEBOUNCE, &mut |_| {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_watch_loop_keeps_watching_after_failed_batch() {
+        let root_dir = PathBuf::from("/repo");
+        let (sender, events) = mpsc::channel();
+        sender.send(WatchEvent::Changed(vec![root_dir.join("src/a.rs")])).unwrap();
+
+        let mut calls = 0;
+        watch_loop(&events, &root_dir, DEBOUNCE, &mut |_| {
+            calls += 1;
+            if calls == 1 {
+                sender.send(WatchEvent::Changed(vec![root_dir.join("src/b.rs")])).unwrap();
+                anyhow::bail!("broken config");
+            }
+            sender.send(WatchEvent::Stop).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_file_change_triggers_reindex() {
+        let temp_dir = TempDir::new().unwrap();
+        let root_dir = temp_dir.path().canonicalize().unwrap();
+        let (sender, events) = mpsc::channel();
+        let _watcher = start_watcher(&root_dir, sender.clone()).unwrap();
+        let guard = sender.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_secs(10));
+            guard.send(WatchEvent::Stop).ok();
+        });
+
+        fs::write(root_dir.join("driver.rs"), "").unwrap();
+
+        let mut batches = Vec::new();
+        watch_loop(&events, &root_dir, DEBOUNCE, &mut |paths| {
+            batches.push(paths.to_vec());
+            sender.send(WatchEvent::Stop).unwrap();
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(batches.len(), 1);
+        assert!(batches[0].contains(&root_dir.join("driver.rs")));
+    }
+}
@@ -0,0 +1,155 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use log::info;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Retention {
+    pub keep_days: Option<u32>,
+    pub keep_count: Option<usize>,
+}
+
+impl Retention {
+    pub fn is_empty(&self) -> bool {
+        self.keep_days.is_none() && self.keep_count.is_none()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct GcSummary {
+    pub removed: Vec<PathBuf>,
+    pub bytes: u64,
+}
+
+pub fn logs_dir(root_dir: &Path) -> PathBuf {
+    root_dir.join(".overcode").join("logs")
+}
+
+fn run_id_at(logs_dir: &Path, now: Duration, pid: u32) -> String {
+    let base = format!("{}.{:03}-{}", now.as_secs(), now.subsec_millis(), pid);
+    let mut run_id = base.clone();
+    let mut suffix = 1;
+    while logs_dir.join(&run_id).exists() {
+        run_id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    run_id
+}
+
+pub fn new_run_id(logs_dir: &Path) -> Result<String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System clock is before the UNIX epoch")?;
+    Ok(run_id_at(logs_dir, now, std::process::id()))
+}
+
+fn run_started_at(run_id: &str) -> Option<Duration> {
+    let timestamp = run_id.split('-').next()?;
+    let (secs, millis) = timestamp.split_once('.').unwrap_or((timestamp, "0"));
+    let millis = millis.parse::<u64>().ok().filter(|millis| *millis < 1000)?;
+    Some(Duration::from_secs(secs.parse().ok()?) + Duration::from_millis(millis))
+}
+
+fn list_runs(logs_dir: &Path) -> Result<Vec<(Duration, PathBuf)>> {
+    if !logs_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut runs = Vec::new();
+    for entry in fs::read_dir(logs_dir)
+        .with_context(|| format!("Failed to read log directory: {}", logs_dir.display()))?
+    {
+        let path = entry?.path();
+        let started_at = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(run_started_at);
+        if let (Some(started_at), true) = (started_at, path.is_dir()) {
+            runs.push((started_at, path));
+        }
+    }
+    Ok(runs)
+}
+
+fn expired_runs(mut runs: Vec<(Duration, PathBuf)>, retention: Retention, now: u64) -> Vec<PathBuf> {
+    runs.sort_by(|(a, a_path), (b, b_path)| b.cmp(a).then_with(|| b_path.cmp(a_path)));
+    let cutoff = retention
+        .keep_days
+        .map(|days| now.saturating_sub(u64::from(days) * SECS_PER_DAY));
+
+    runs.into_iter()
+        .enumerate()
+        .filter(|(index, (started_at, _))| {
+            retention.keep_count.is_some_and(|count| *index >= count)
+                || cutoff.is_some_and(|cutoff| started_at.as_secs() < cutoff)
+        })
+        .map(|(_, (_, path))| path)
+        .collect()
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            _ => entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
+fn prune_logs_at(root_dir: &Path, retention: Retention, dry_run: bool, now: u64) -> Result<GcSummary> {
+    let runs = list_runs(&logs_dir(root_dir))?;
+    let mut summary = GcSummary::default();
+
+    for path in expired_runs(runs, retention, now) {
+        summary.bytes += dir_size(&path);
+        if !dry_run {
+            info!("Removing log directory: {}", path.display());
+            fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove log directory: {}", path.display()))?;
+        }
+        summary.removed.push(path);
+    }
+
+    Ok(summary)
+}
+
+fn format_gc_report(summary: &GcSummary, dry_run: bool) -> String {
+    let mut report = String::new();
+    if dry_run {
+        for path in &summary.removed {
+            report.push_str(&format!("Would remove log directory: {}\n", path.display()));
+        }
+        report.push_str(&format!(
+            "{} log directories would be removed, {} bytes would be reclaimed\n",
+            summary.removed.len(),
+            summary.bytes
+        ));
+    } else {
+        report.push_str(&format!("{} log directories removed, {} bytes reclaimed\n", summary.removed.len(), summary.bytes));
+    }
+    report
+}
+
+pub fn process_gc_logs(root_dir: &Path, retention: Retention, dry_run: bool) -> Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let summary = prune_logs_at(root_dir, retention, dry_run, now)?;
+
+    write!(std::io::stdout(), "{}", format_gc_report(&summary, dry_run))
+        .context("Failed to write log gc summary")?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "log_gc/driver/retention/retention.rs"]
+mod driver_retention_retention;
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use crate::log_gc::{format_gc_report, logs_dir, prune_logs_at, run_id_at, Retention};
+
+    const DAY: u64 = 24 * 60 * 60;
+    const NOW: u64 = 100 * DAY;
+
+    fn create_runs(root_dir: &Path, run_ids: &[u64]) {
+        for run_id in run_ids {
+            let dir = logs_dir(root_dir).join(run_id.to_string());
+            fs::create_dir_all(dir.join("tests")).unwrap();
+            fs::write(dir.join("tests").join("a.sh.log"), "0123456789").unwrap();
+        }
+    }
+
+    fn remaining(root_dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(logs_dir(root_dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_keep_count_removes_oldest_runs() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 3, NOW - 2, NOW - 1]);
+
+        let retention = Retention { keep_days: None, keep_count: Some(2) };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 1);
+        assert_eq!(summary.bytes, 10);
+        assert_eq!(remaining(temp_dir.path()), vec![(NOW - 2).to_string(), (NOW - 1).to_string()]);
+    }
+
+    #[test]
+    fn test_keep_days_removes_runs_older_than_cutoff() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 10 * DAY, NOW - 8 * DAY, NOW - DAY]);
+
+        let retention = Retention { keep_days: Some(7), keep_count: None };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(remaining(temp_dir.path()), vec![(NOW - DAY).to_string()]);
+    }
+
+    #[test]
+    fn test_keep_days_and_keep_count_both_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 10 * DAY, NOW - 3, NOW - 2, NOW - 1]);
+
+        let retention = Retention { keep_days: Some(7), keep_count: Some(2) };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(remaining(temp_dir.path()), vec![(NOW - 2).to_string(), (NOW - 1).to_string()]);
+    }
+
+    #[test]
+    fn test_dry_run_keeps_files() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 3, NOW - 2, NOW - 1]);
+
+        let retention = Retention { keep_days: None, keep_count: Some(1) };
+        let summary = prune_logs_at(temp_dir.path(), retention, true, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(summary.bytes, 20);
+        assert_eq!(remaining(temp_dir.path()).len(), 3);
+    }
+
+    #[test]
+    fn test_dry_run_report_lists_directories_and_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 3, NOW - 2, NOW - 1]);
+
+        let retention = Retention { keep_days: None, keep_count: Some(1) };
+        let summary = prune_logs_at(temp_dir.path(), retention, true, NOW).unwrap();
+        let report = format_gc_report(&summary, true);
+
+        let logs = logs_dir(temp_dir.path());
+        assert_eq!(
+            report,
+            format!(
+                "Would remove log directory: {}\nWould remove log directory: {}\n2 log directories would be removed, 20 bytes would be reclaimed\n",
+                logs.join((NOW - 2).to_string()).display(),
+                logs.join((NOW - 3).to_string()).display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_report_after_removal_has_only_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 2, NOW - 1]);
+
+        let retention = Retention { keep_days: None, keep_count: Some(1) };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(format_gc_report(&summary, false), "1 log directories removed, 10 bytes reclaimed\n");
+    }
+
+    #[test]
+    fn test_non_run_entries_are_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        create_runs(temp_dir.path(), &[NOW - 2, NOW - 1]);
+        fs::create_dir_all(logs_dir(temp_dir.path()).join("keep-me")).unwrap();
+        fs::write(logs_dir(temp_dir.path()).join("123"), "not a directory").unwrap();
+
+        let retention = Retention { keep_days: None, keep_count: Some(0) };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(remaining(temp_dir.path()), vec!["123".to_string(), "keep-me".to_string()]);
+    }
+
+    #[test]
+    fn test_missing_logs_dir_is_not_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let retention = Retention { keep_days: Some(1), keep_count: None };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert!(summary.removed.is_empty());
+    }
+
+    #[test]
+    fn test_run_id_has_millisecond_resolution_and_pid() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = run_id_at(temp_dir.path(), Duration::from_millis(1_700_000_000_123), 42);
+        let second = run_id_at(temp_dir.path(), Duration::from_millis(1_700_000_000_456), 42);
+
+        assert_eq!(first, "1700000000.123-42");
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_run_id_falls_back_when_directory_exists() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = Duration::from_millis(1_700_000_000_123);
+        fs::create_dir_all(temp_dir.path().join("1700000000.123-42")).unwrap();
+        fs::create_dir_all(temp_dir.path().join("1700000000.123-42-1")).unwrap();
+
+        assert_eq!(run_id_at(temp_dir.path(), now, 42), "1700000000.123-42-2");
+    }
+
+    #[test]
+    fn test_keep_count_orders_runs_within_the_same_second() {
+        let temp_dir = TempDir::new().unwrap();
+        for run_id in [format!("{}.100-1", NOW - 1), format!("{}.900-1", NOW - 1), format!("{}.500-2", NOW - 1)] {
+            fs::create_dir_all(logs_dir(temp_dir.path()).join(run_id)).unwrap();
+        }
+
+        let retention = Retention { keep_days: Some(1), keep_count: Some(1) };
+        let summary = prune_logs_at(temp_dir.path(), retention, false, NOW).unwrap();
+
+        assert_eq!(summary.removed.len(), 2);
+        assert_eq!(remaining(temp_dir.path()), vec![format!("{}.900-1", NOW - 1)]);
+    }
+}
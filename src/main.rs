@@ -1,13 +1,34 @@
+mod artifact;
+mod atomic_file;
+mod bench;
 mod cli;
 mod config;
+mod config_snapshot;
+mod env_file;
+mod failure_excerpt;
+mod index;
+mod log_gc;
+mod monorepo;
 mod overcode;
+mod podman_api;
+mod podman_command;
+mod podman_container;
 mod podman_image;
 mod podman_image_download;
 mod podman_install;
+mod podman_machine;
 mod podman_mount;
+mod podman_volume;
+mod replace_rule;
 mod run;
+mod run_history;
+mod scheduler;
+mod serve;
 mod test;
 
-fn main() -> anyhow::Result<()> {
-    overcode::main()
+fn main() {
+    if let Err(err) = overcode::main() {
+        err.report();
+        std::process::exit(err.exit_code());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,147 @@
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+use crate::config::Config;
+use crate::podman_command;
+use crate::podman_image;
+use crate::podman_volume;
+use crate::scheduler;
+use crate::test::{process_test, TestOptions, TestsFailed};
+
+const CONFIG_FILE_NAME: &str = "overcode.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProjectStatus {
+    Passed,
+    Failed,
+    NotRun,
+}
+
+#[derive(Debug)]
+struct ProjectResult {
+    root_dir: PathBuf,
+    status: ProjectStatus,
+    error: Option<String>,
+}
+
+fn discover_projects(root_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut builder = WalkBuilder::new(root_dir);
+    builder
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".overcode" && entry.file_name() != ".git");
+
+    let mut config_paths = Vec::new();
+    for result in builder.build() {
+        let entry = result?;
+        if entry.file_name() == CONFIG_FILE_NAME && entry.path().is_file() {
+            config_paths.push(entry.path().to_path_buf());
+        }
+    }
+
+    config_paths.sort();
+    Ok(config_paths)
+}
+
+fn test_project(config: &Config, root_dir: &Path, ensured_images: &mut HashSet<String>, options: &TestOptions) -> anyhow::Result<()> {
+    podman_command::configure(config.podman.as_ref());
+    scheduler::configure(options.jobs, config.podman.as_ref().and_then(|p| p.max_parallel));
+
+    let images: HashSet<String> = podman_image::collect_images(config)
+        .into_iter()
+        .filter(|image| !ensured_images.contains(image))
+        .collect();
+    if !images.is_empty() {
+        podman_image::ensure_image_set(&images, config.podman.as_ref(), options.refresh_images)?;
+        ensured_images.extend(images);
+    }
+
+    podman_volume::ensure_volumes(config, root_dir)?;
+    process_test(config, root_dir, options)
+}
+
+fn run_projects(
+    config_paths: &[PathBuf],
+    load_config: &dyn Fn(&Path) -> anyhow::Result<Config>,
+    options: &TestOptions,
+    fail_fast: bool,
+) -> Vec<ProjectResult> {
+    let mut results = Vec::new();
+    let mut ensured_images = HashSet::new();
+    let mut stopped = false;
+
+    for config_path in config_paths {
+        let root_dir = config_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+        if stopped {
+            results.push(ProjectResult { root_dir, status: ProjectStatus::NotRun, error: None });
+            continue;
+        }
+
+        info!("Testing project: {}", root_dir.display());
+        let outcome = load_config(config_path)
+            .and_then(|config| test_project(&config, &root_dir, &mut ensured_images, options));
+
+        match outcome {
+            Ok(()) => results.push(ProjectResult { root_dir, status: ProjectStatus::Passed, error: None }),
+            Err(e) => {
+                warn!("✗ Project failed: {}: {:#}", root_dir.display(), e);
+                results.push(ProjectResult { root_dir, status: ProjectStatus::Failed, error: Some(format!("{:#}", e)) });
+                stopped = fail_fast;
+            }
+        }
+    }
+
+    results
+}
+
+fn format_project_summary(results: &[ProjectResult], base_dir: &Path) -> String {
+    let mut summary = format!("{:<6}  PROJECT\n", "STATUS");
+    for result in results {
+        let status = match result.status {
+            ProjectStatus::Passed => "PASS",
+            ProjectStatus::Failed => "FAIL",
+            ProjectStatus::NotRun => "SKIP",
+        };
+        let project = match result.root_dir.strip_prefix(base_dir) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => result.root_dir.display().to_string(),
+        };
+        match result.error {
+            Some(ref error) => summary.push_str(&format!("{:<6}  {} ({})\n", status, project, error)),
+            None => summary.push_str(&format!("{:<6}  {}\n", status, project)),
+        }
+    }
+    summary
+}
+
+pub fn process_test_recursive(
+    root_dir: &Path,
+    load_config: &dyn Fn(&Path) -> anyhow::Result<Config>,
+    options: &TestOptions,
+    fail_fast: bool,
+) -> anyhow::Result<()> {
+    let config_paths = discover_projects(root_dir)?;
+    if config_paths.is_empty() {
+        warn!("No {} found under {}. Nothing to test.", CONFIG_FILE_NAME, root_dir.display());
+        return Ok(());
+    }
+
+    info!("Found {} project(s) to test", config_paths.len());
+
+    let results = run_projects(&config_paths, load_config, options, fail_fast);
+    info!("Project results:\n{}", format_project_summary(&results, root_dir));
+
+    let failed = results.iter().filter(|result| result.status == ProjectStatus::Failed).count();
+    if failed > 0 {
+        return Err(TestsFailed(format!("Some projects failed: {} out of {} failed", failed, results.len())).into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "monorepo/driver/discover/discover.rs"]
+mod driver_discover_discover;
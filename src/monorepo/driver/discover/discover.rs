@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::monorepo::{discover_projects, format_project_summary, run_projects, ProjectStatus};
+    use crate::test::TestOptions;
+
+    const PASSING_PROJECT: &str = r#"
+[command.test]
+command = "true"
+args = []
+"#;
+
+    fn write_project(root: &Path, dir: &str, content: &str) -> PathBuf {
+        let project_dir = root.join(dir);
+        fs::create_dir_all(&project_dir).unwrap();
+        let config_path = project_dir.join("overcode.toml");
+        fs::write(&config_path, content).unwrap();
+        config_path
+    }
+
+    #[test]
+    fn test_discover_projects_finds_nested_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        write_project(temp_dir.path(), "services/a", PASSING_PROJECT);
+        write_project(temp_dir.path(), "services/b", PASSING_PROJECT);
+        write_project(temp_dir.path(), ".overcode/logs", PASSING_PROJECT);
+        write_project(temp_dir.path(), "vendor/c", PASSING_PROJECT);
+        fs::write(temp_dir.path().join(".gitignore"), "/vendor/\n").unwrap();
+
+        let projects = discover_projects(temp_dir.path()).unwrap();
+
+        assert_eq!(
+            projects,
+            vec![
+                temp_dir.path().join("services/a/overcode.toml"),
+                temp_dir.path().join("services/b/overcode.toml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_projects_continues_after_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let failing = write_project(temp_dir.path(), "services/a", "");
+        let passing = write_project(temp_dir.path(), "services/b", PASSING_PROJECT);
+        let loaded = RefCell::new(Vec::new());
+        let loader = |path: &Path| {
+            loaded.borrow_mut().push(path.to_path_buf());
+            Config::load(path)
+        };
+
+        let results = run_projects(&[failing.clone(), passing.clone()], &loader, &TestOptions::default(), false);
+
+        assert_eq!(results[0].status, ProjectStatus::Failed);
+        assert!(results[0].error.as_deref().unwrap().contains("[command.test]"));
+        assert_eq!(results[1].status, ProjectStatus::Passed);
+        assert_eq!(*loaded.borrow(), vec![failing, passing]);
+    }
+
+    #[test]
+    fn test_run_projects_fail_fast_skips_remaining() {
+        let temp_dir = TempDir::new().unwrap();
+        let failing = write_project(temp_dir.path(), "services/a", "");
+        let passing = write_project(temp_dir.path(), "services/b", PASSING_PROJECT);
+        let loaded = RefCell::new(Vec::new());
+        let loader = |path: &Path| {
+            loaded.borrow_mut().push(path.to_path_buf());
+            Config::load(path)
+        };
+
+        let results = run_projects(&[failing.clone(), passing], &loader, &TestOptions::default(), true);
+
+        assert_eq!(results[0].status, ProjectStatus::Failed);
+        assert_eq!(results[1].status, ProjectStatus::NotRun);
+        assert_eq!(*loaded.borrow(), vec![failing]);
+    }
+
+    #[test]
+    fn test_format_project_summary_lists_relative_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let failing = write_project(temp_dir.path(), "services/a", "");
+        let passing = write_project(temp_dir.path(), "services/b", PASSING_PROJECT);
+        let results = run_projects(&[failing, passing], &Config::load, &TestOptions::default(), false);
+
+        let summary = format_project_summary(&results, temp_dir.path());
+        let lines: Vec<&str> = summary.lines().collect();
+
+        assert_eq!(lines[0], "STATUS  PROJECT");
+        assert!(lines[1].starts_with("FAIL    services/a ("));
+        assert_eq!(lines[2], "PASS    services/b");
+    }
+}
@@ -1,27 +1,239 @@
+use anyhow::Context;
+use log::error;
+use std::io::Write;
+use std::path::Path;
+use crate::bench::{process_bench, BenchOptions};
 use crate::cli::{Cli, Command};
-use crate::test::process_test;
-use crate::run::process_run;
+use crate::config::{Config, InvalidConfig, ValidationContext};
+use crate::podman_command::EngineUnavailable;
+use crate::podman_install::StatusFormat;
+use crate::test::{process_test, TestOptions, TestsFailed};
+use crate::run::{process_run, process_run_history, RunFailed, RunOptions, RunTimedOut, TIMEOUT_EXIT_CODE};
 
-pub fn main() -> anyhow::Result<()> {
-    env_logger::Builder::from_default_env().try_init().ok();
-    
-    let cli = Cli::parse()?;
+pub const TEST_FAILURE_EXIT_CODE: i32 = 1;
+pub const CONFIG_ERROR_EXIT_CODE: i32 = 2;
+pub const ENVIRONMENT_ERROR_EXIT_CODE: i32 = 3;
+pub const INTERNAL_ERROR_EXIT_CODE: i32 = 4;
 
+#[derive(Debug)]
+pub enum OvercodeError {
+    TestFailure(anyhow::Error),
+    Config(anyhow::Error),
+    Environment(anyhow::Error),
+    TimedOut(anyhow::Error),
+    CommandExited(i32, anyhow::Error),
+    Internal(anyhow::Error),
+}
+
+impl OvercodeError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::TestFailure(_) => TEST_FAILURE_EXIT_CODE,
+            Self::Config(_) => CONFIG_ERROR_EXIT_CODE,
+            Self::Environment(_) => ENVIRONMENT_ERROR_EXIT_CODE,
+            Self::TimedOut(_) => TIMEOUT_EXIT_CODE,
+            Self::CommandExited(code, _) => *code,
+            Self::Internal(_) => INTERNAL_ERROR_EXIT_CODE,
+        }
+    }
+
+    pub fn report(&self) {
+        match self {
+            Self::TimedOut(err) => error!("{}", err),
+            Self::TestFailure(err)
+            | Self::Config(err)
+            | Self::Environment(err)
+            | Self::CommandExited(_, err)
+            | Self::Internal(err) => {
+                eprintln!("Error: {:?}", err);
+            }
+        }
+    }
+}
+
+impl From<anyhow::Error> for OvercodeError {
+    fn from(err: anyhow::Error) -> Self {
+        let exit_code = err
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<RunFailed>())
+            .and_then(|failed| failed.code)
+            .filter(|code| *code != 0);
+        if err.chain().any(|cause| cause.is::<RunTimedOut>()) {
+            Self::TimedOut(err)
+        } else if let Some(code) = exit_code {
+            Self::CommandExited(code, err)
+        } else if err.chain().any(|cause| cause.is::<TestsFailed>() || cause.is::<RunFailed>()) {
+            Self::TestFailure(err)
+        } else if err.chain().any(|cause| cause.is::<InvalidConfig>()) {
+            Self::Config(err)
+        } else if err.chain().any(|cause| cause.is::<EngineUnavailable>()) {
+            Self::Environment(err)
+        } else {
+            Self::Internal(err)
+        }
+    }
+}
+
+pub fn main() -> Result<(), OvercodeError> {
+    let cli = Cli::parse().map_err(OvercodeError::Config)?;
+
+    let mut logger = match cli.log_level {
+        Some(level) => {
+            let mut builder = env_logger::Builder::new();
+            builder.filter_level(level);
+            builder
+        }
+        None => env_logger::Builder::from_default_env(),
+    };
+    logger.try_init().ok();
+
+    execute(&cli, &Config::load)
+}
+
+fn test_options(cli: &Cli) -> TestOptions {
+    TestOptions {
+        check_command: cli.check_command,
+        filter_tags: cli.tags.clone(),
+        deadline: cli.deadline,
+        format: cli.format,
+        jobs: cli.jobs,
+        include_excluded: cli.include_excluded,
+        refresh_images: cli.refresh_images,
+        driver_filter: None,
+    }
+}
+
+fn configure_podman(cli: &Cli, config: &Config) {
+    let podman = config.podman.as_ref();
+    crate::podman_command::configure(podman);
+    crate::scheduler::configure(cli.jobs, podman.and_then(|p| p.max_parallel));
+}
+
+fn load_validated_config(cli: &Cli, load_config: &dyn Fn(&Path) -> anyhow::Result<Config>, config_path: &Path) -> anyhow::Result<Config> {
+    let config = load_config(config_path)?;
+    config.validate(&ValidationContext { no_podman: cli.no_podman, root_dir: config_path.parent() })
+        .with_context(|| format!("Invalid config file: {:?}", config_path))?;
+    Ok(config)
+}
+
+fn execute(cli: &Cli, load_config: &dyn Fn(&Path) -> anyhow::Result<Config>) -> Result<(), OvercodeError> {
     match cli.command {
         Command::Init => {
-            crate::config::Config::init_config(&cli.root_dir)?;
-            crate::podman_install::ensure_podman()?;
-            crate::podman_image::ensure_images(&cli.config_path)?;
+            Config::init_config(&cli.root_dir)?;
+            if !cli.no_gitignore {
+                Config::init_gitignore(&cli.root_dir)?;
+            }
+            if !cli.no_podman {
+                match cli.output_format {
+                    StatusFormat::Human => crate::podman_install::ensure_podman().map_err(OvercodeError::Environment)?,
+                    StatusFormat::Json => {
+                        let status = crate::podman_install::check_podman_status();
+                        writeln!(std::io::stdout(), "{}", status.to_json()?)
+                            .context("Failed to write podman status")?;
+                        status.into_result().map_err(OvercodeError::Environment)?;
+                    }
+                }
+            }
+            let config = load_config(&cli.config_path).map_err(OvercodeError::Config)?;
+            configure_podman(cli, &config);
+            if !cli.no_podman {
+                crate::podman_machine::ensure_machine(config.podman.as_ref()).map_err(OvercodeError::Environment)?;
+            }
+            crate::podman_image::ensure_images(&config, cli.refresh_images).map_err(OvercodeError::Environment)?;
+            crate::podman_volume::ensure_volumes(&config, &cli.root_dir).map_err(OvercodeError::Environment)?;
+        }
+        Command::Test if cli.recursive => {
+            let options = test_options(cli);
+            let load_validated = |path: &Path| load_validated_config(cli, load_config, path);
+            crate::monorepo::process_test_recursive(&cli.root_dir, &load_validated, &options, cli.fail_fast)?;
         }
         Command::Test => {
-            crate::config::Config::init_config(&cli.root_dir)?;
-            crate::podman_image::ensure_images(&cli.config_path)?;
-            process_test(&cli.config_path)?;
+            if !cli.no_init {
+                Config::init_config(&cli.root_dir)?;
+            }
+            let config = load_validated_config(cli, load_config, &cli.config_path).map_err(OvercodeError::Config)?;
+            configure_podman(cli, &config);
+            crate::podman_image::ensure_images(&config, cli.refresh_images).map_err(OvercodeError::Environment)?;
+            crate::podman_volume::ensure_volumes(&config, &cli.root_dir).map_err(OvercodeError::Environment)?;
+            let options = test_options(cli);
+            process_test(&config, &cli.root_dir, &options)?;
         }
         Command::Run => {
-            crate::config::Config::init_config(&cli.root_dir)?;
-            crate::podman_image::ensure_images(&cli.config_path)?;
-            process_run(&cli.config_path, &cli.extra_args)?;
+            if let Some(count) = cli.history {
+                return Ok(process_run_history(&cli.root_dir, count)?);
+            }
+            if !cli.no_init {
+                Config::init_config(&cli.root_dir)?;
+            }
+            let config = load_validated_config(cli, load_config, &cli.config_path).map_err(OvercodeError::Config)?;
+            configure_podman(cli, &config);
+            crate::podman_image::ensure_images(&config, cli.refresh_images).map_err(OvercodeError::Environment)?;
+            crate::podman_volume::ensure_volumes(&config, &cli.root_dir).map_err(OvercodeError::Environment)?;
+            let options = RunOptions {
+                name: cli.run_name.clone(),
+                timeout: cli.timeout,
+            };
+            process_run(&config, &cli.root_dir, &cli.extra_args, &options)?;
+        }
+        Command::Bench => {
+            if !cli.no_init {
+                Config::init_config(&cli.root_dir)?;
+            }
+            let config = load_validated_config(cli, load_config, &cli.config_path).map_err(OvercodeError::Config)?;
+            configure_podman(cli, &config);
+            crate::podman_image::ensure_images(&config, cli.refresh_images).map_err(OvercodeError::Environment)?;
+            crate::podman_volume::ensure_volumes(&config, &cli.root_dir).map_err(OvercodeError::Environment)?;
+            let bench_options = BenchOptions {
+                compare: cli.compare,
+                threshold_percent: cli.threshold,
+                baseline: cli.baseline.clone(),
+            };
+            process_bench(&config, &cli.root_dir, &test_options(cli), &bench_options)?;
+        }
+        Command::Gc => {
+            let retention = crate::log_gc::Retention { keep_days: cli.gc_keep_days, keep_count: cli.gc_keep_count };
+            if !cli.gc_volumes && retention.is_empty() {
+                return Err(OvercodeError::Config(anyhow::anyhow!("Nothing to collect. Use 'gc --volumes' to remove unused volumes or 'gc --keep-days N'/'gc --keep-count N' to prune test logs")));
+            }
+            if !retention.is_empty() {
+                crate::log_gc::process_gc_logs(&cli.root_dir, retention, cli.dry_run)?;
+            }
+            if cli.gc_volumes {
+                let config = load_config(&cli.config_path).map_err(OvercodeError::Config)?;
+                configure_podman(cli, &config);
+                crate::podman_volume::process_gc_volumes(&config, &cli.root_dir, cli.dry_run).map_err(OvercodeError::Environment)?;
+            }
+        }
+        Command::Serve => {
+            let config = load_validated_config(cli, load_config, &cli.config_path).map_err(OvercodeError::Config)?;
+            configure_podman(cli, &config);
+            crate::podman_image::ensure_images(&config, cli.refresh_images).map_err(OvercodeError::Environment)?;
+            crate::podman_volume::ensure_volumes(&config, &cli.root_dir).map_err(OvercodeError::Environment)?;
+            let mut state = crate::serve::ServeState::new(config, &cli.config_path, &cli.root_dir, test_options(cli));
+            crate::serve::process_serve(&mut state)?;
+        }
+        Command::HistoryTag { timestamp, ref name } => {
+            crate::bench::tag_history(&cli.root_dir, timestamp, name)?;
+        }
+        Command::HistoryUntag { ref name } => {
+            crate::bench::untag_history(&cli.root_dir, name)?;
+        }
+        Command::Export { ref output } => {
+            crate::bench::export_history(&cli.root_dir, output)?;
+        }
+        Command::Import { ref from } => {
+            crate::bench::import_history(&cli.root_dir, from)?;
+        }
+        Command::Index { watch, debounce } => {
+            let config = load_config(&cli.config_path).map_err(OvercodeError::Config)?;
+            if watch {
+                crate::index::process_index_watch(&config, &cli.root_dir, debounce)?;
+            } else {
+                crate::index::process_index(&config, &cli.root_dir)?;
+            }
+        }
+        Command::MigrateConfig => {
+            Config::migrate_config_file(&cli.config_path).map_err(OvercodeError::Config)?;
         }
     }
 
@@ -36,6 +248,10 @@ mod driver_cli_cli;
 #[path = "overcode/driver/config/config.rs"]
 mod driver_config_config;
 
+#[cfg(test)]
+#[path = "overcode/driver/exit_code/exit_code.rs"]
+mod driver_exit_code_exit_code;
+
 #[cfg(test)]
 #[path = "overcode/driver/podman_image/podman_image.rs"]
 mod driver_podman_image_podman_image;
@@ -1,7 +1,17 @@
 #[cfg(test)]
 mod tests {
+    use std::fs;
     use std::path::PathBuf;
+    use std::time::Duration;
+    use log::LevelFilter;
+    use tempfile::TempDir;
     use crate::cli::{Cli, Command};
+    use crate::podman_install::StatusFormat;
+    use crate::test::OutputFormat;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
 
     #[test]
     fn test_cli_parse() {
@@ -33,6 +43,30 @@ mod tests {
             root_dir: PathBuf::from("/tmp"),
             config_path: PathBuf::from("/tmp/overcode.toml"),
             extra_args: vec![],
+            no_gitignore: false,
+            log_level: None,
+            check_command: false,
+            run_name: None,
+            gc_volumes: false,
+            gc_keep_days: None,
+            gc_keep_count: None,
+            dry_run: false,
+            tags: vec![],
+            deadline: None,
+            recursive: false,
+            fail_fast: false,
+            no_init: false,
+            no_podman: false,
+            format: OutputFormat::Human,
+            jobs: None,
+            compare: false,
+            threshold: None,
+            baseline: None,
+            history: None,
+            output_format: StatusFormat::Human,
+            timeout: None,
+            include_excluded: false,
+            refresh_images: false,
         };
         
         assert_eq!(cli.command, Command::Init);
@@ -43,5 +77,632 @@ mod tests {
         let cli_str = format!("{:?}", cli);
         assert!(!cli_str.is_empty());
     }
-}
 
+    #[test]
+    fn test_cli_parse_from_populates_root_dir_and_config_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        let canonical_root = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(cli.command, Command::Test);
+        assert_eq!(cli.root_dir, canonical_root);
+        assert_eq!(cli.config_path, canonical_root.join("overcode.toml"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_init_uses_given_config_path() {
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "init",
+            "--config",
+            "/tmp/project/overcode.toml",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.root_dir, PathBuf::from("/tmp/project"));
+        assert_eq!(cli.config_path, PathBuf::from("/tmp/project/overcode.toml"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_collects_extra_args() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--",
+            "--config",
+            "extra",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.command, Command::Run);
+        assert_eq!(cli.config_path, temp_dir.path().canonicalize().unwrap().join("overcode.toml"));
+        assert_eq!(cli.extra_args, vec!["--config".to_string(), "extra".to_string()]);
+    }
+
+    #[test]
+    fn test_cli_parse_from_global_log_flags() {
+        let cases: &[(&[&str], Option<LevelFilter>)] = &[
+            (&["overcode", "init"], None),
+            (&["overcode", "-q", "init"], Some(LevelFilter::Error)),
+            (&["overcode", "--quiet", "init"], Some(LevelFilter::Error)),
+            (&["overcode", "-v", "init"], Some(LevelFilter::Debug)),
+            (&["overcode", "-vv", "init"], Some(LevelFilter::Trace)),
+            (&["overcode", "-v", "-v", "init"], Some(LevelFilter::Trace)),
+        ];
+
+        for (argv, expected) in cases {
+            let cli = Cli::parse_from(args(argv)).unwrap();
+            assert_eq!(cli.command, Command::Init, "{:?}", argv);
+            assert_eq!(cli.log_level, *expected, "{:?}", argv);
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_from_rejects_quiet_with_verbose() {
+        let result = Cli::parse_from(args(&["overcode", "-q", "-v", "init"]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_rejects_unknown_global_flag() {
+        let result = Cli::parse_from(args(&["overcode", "--bogus", "init"]));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("--bogus"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_check_command_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--check-command",
+        ]))
+        .unwrap();
+
+        assert!(cli.check_command);
+    }
+
+    #[test]
+    fn test_cli_parse_from_repeated_tag_option() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--tag",
+            "database",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--tag",
+            "network",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.tags, vec!["database", "network"]);
+    }
+
+    #[test]
+    fn test_cli_parse_from_deadline_option() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--deadline",
+            "90",
+        ]))
+        .unwrap();
+        assert_eq!(cli.deadline, Some(Duration::from_secs(90)));
+
+        let result = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--deadline",
+            "soon",
+        ]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_recursive_flags() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--recursive",
+            "--fail-fast",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert!(cli.recursive);
+        assert!(cli.fail_fast);
+    }
+
+    #[test]
+    fn test_cli_parse_from_format_option() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--format",
+            "tap",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.format, OutputFormat::Tap);
+    }
+
+    #[test]
+    fn test_cli_parse_from_jobs_option() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--jobs",
+            "4",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.jobs, Some(4));
+    }
+
+    #[test]
+    fn test_cli_parse_from_bench_compare() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "bench",
+            "--compare",
+            "--threshold",
+            "5.5",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.command, Command::Bench);
+        assert!(cli.compare);
+        assert_eq!(cli.threshold, Some(5.5));
+    }
+
+    #[test]
+    fn test_cli_parse_from_positional_config_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            config_path.to_str().unwrap(),
+            "--tag",
+            "database",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.command, Command::Test);
+        assert_eq!(cli.config_path, config_path.canonicalize().unwrap());
+        assert_eq!(cli.tags, vec!["database".to_string()]);
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_positional_config_path_then_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            config_path.to_str().unwrap(),
+            "serve",
+            "--",
+            "other.toml",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.config_path, config_path.canonicalize().unwrap());
+        assert_eq!(cli.run_name, Some("serve".to_string()));
+        assert_eq!(cli.extra_args, vec!["other.toml".to_string()]);
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_name_is_not_config_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "serve",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.config_path, config_path.canonicalize().unwrap());
+        assert_eq!(cli.run_name, Some("serve".to_string()));
+    }
+
+    #[test]
+    fn test_cli_parse_from_explicit_config_overrides_positional() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "missing/overcode.toml",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.config_path, config_path.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_cli_parse_from_positional_config_path_must_exist() {
+        let result = Cli::parse_from(args(&["overcode", "test", "missing/overcode.toml"]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_init_positional_config_path() {
+        let cli = Cli::parse_from(args(&["overcode", "init", "new/overcode.toml"])).unwrap();
+
+        assert_eq!(cli.command, Command::Init);
+        assert_eq!(cli.config_path, PathBuf::from("new/overcode.toml"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let with_count = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--history",
+            "3",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+        let without_count = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--history",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(with_count.history, Some(3));
+        assert_eq!(with_count.run_name, None);
+        assert_eq!(without_count.history, Some(10));
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--timeout",
+            "90",
+            "serve",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.timeout, Some(Duration::from_secs(90)));
+        assert_eq!(cli.run_name, Some("serve".to_string()));
+    }
+
+    #[test]
+    fn test_cli_parse_from_migrate_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&["overcode", "migrate-config", config_path.to_str().unwrap()])).unwrap();
+
+        assert_eq!(cli.command, Command::MigrateConfig);
+        assert_eq!(cli.config_path, config_path.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_cli_parse_from_include_excluded_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let default = Cli::parse_from(args(&["overcode", "test", "--config", config_path.to_str().unwrap()])).unwrap();
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--include-excluded",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert!(!default.include_excluded);
+        assert!(cli.include_excluded);
+    }
+
+    #[test]
+    fn test_cli_parse_from_refresh_images_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let default = Cli::parse_from(args(&["overcode", "run", "--config", config_path.to_str().unwrap()])).unwrap();
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--refresh-images",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert!(!default.refresh_images);
+        assert!(cli.refresh_images);
+    }
+
+    #[test]
+    fn test_cli_parse_from_gc_retention_options() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let default = Cli::parse_from(args(&["overcode", "gc", "--config", config_path.to_str().unwrap()])).unwrap();
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "gc",
+            "--keep-days",
+            "7",
+            "--keep-count",
+            "20",
+            "--dry-run",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(default.gc_keep_days, None);
+        assert_eq!(default.gc_keep_count, None);
+        assert!(!default.dry_run);
+        assert_eq!(cli.gc_keep_days, Some(7));
+        assert_eq!(cli.gc_keep_count, Some(20));
+        assert!(cli.dry_run);
+    }
+
+    #[test]
+    fn test_cli_parse_from_keep_days_rejects_non_number() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let result = Cli::parse_from(args(&[
+            "overcode",
+            "gc",
+            "--keep-days",
+            "week",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_history_tag_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let tag = Cli::parse_from(args(&[
+            "overcode",
+            "history",
+            "tag",
+            "1700000000",
+            "baseline",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+        let untag = Cli::parse_from(args(&[
+            "overcode",
+            "history",
+            "untag",
+            "baseline",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(tag.command, Command::HistoryTag { timestamp: 1_700_000_000, name: "baseline".to_string() });
+        assert_eq!(untag.command, Command::HistoryUntag { name: "baseline".to_string() });
+        assert_eq!(tag.root_dir, temp_dir.path().canonicalize().unwrap());
+    }
+
+    #[test]
+    fn test_cli_parse_from_history_rejects_bad_arguments() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        for bad in [&["history"][..], &["history", "tag", "yesterday", "baseline"], &["history", "untag"]] {
+            let mut argv = vec!["overcode"];
+            argv.extend_from_slice(bad);
+            argv.extend_from_slice(&["--config", config_path.to_str().unwrap()]);
+            assert!(Cli::parse_from(args(&argv)).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_from_baseline_option() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "bench",
+            "--baseline",
+            "release",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.baseline.as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_export_and_import() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let export_default = Cli::parse_from(args(&["overcode", "export", "--config", config_path.to_str().unwrap()])).unwrap();
+        let export = Cli::parse_from(args(&["overcode", "export", "--output", "/tmp/out", "--config", config_path.to_str().unwrap()])).unwrap();
+        let import = Cli::parse_from(args(&["overcode", "import", "--from", "/tmp/in", "--config", config_path.to_str().unwrap()])).unwrap();
+        let import_missing = Cli::parse_from(args(&["overcode", "import", "--config", config_path.to_str().unwrap()]));
+
+        assert_eq!(export_default.command, Command::Export { output: PathBuf::from(".") });
+        assert_eq!(export.command, Command::Export { output: PathBuf::from("/tmp/out") });
+        assert_eq!(import.command, Command::Import { from: PathBuf::from("/tmp/in") });
+        assert!(import_missing.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let index = Cli::parse_from(args(&["overcode", "index", "--config", config_path.to_str().unwrap()])).unwrap();
+        let watch = Cli::parse_from(args(&["overcode", "index", "--watch", "--debounce", "200", "--config", config_path.to_str().unwrap()])).unwrap();
+        let invalid = Cli::parse_from(args(&["overcode", "index", "--watch", "--debounce", "soon", "--config", config_path.to_str().unwrap()]));
+
+        assert_eq!(index.command, Command::Index { watch: false, debounce: Duration::from_millis(500) });
+        assert_eq!(watch.command, Command::Index { watch: true, debounce: Duration::from_millis(200) });
+        assert!(invalid.unwrap_err().to_string().contains("--debounce expects a number of milliseconds"));
+    }
+
+    #[test]
+    fn test_cli_parse_from_tag_requires_value() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let result = Cli::parse_from(args(&[
+            "overcode",
+            "test",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--tag",
+        ]));
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--config",
+            config_path.to_str().unwrap(),
+            "serve",
+            "--",
+            "migrate",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.run_name, Some("serve".to_string()));
+        assert_eq!(cli.extra_args, vec!["migrate".to_string()]);
+    }
+
+    #[test]
+    fn test_cli_parse_from_run_without_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let cli = Cli::parse_from(args(&[
+            "overcode",
+            "run",
+            "--config",
+            config_path.to_str().unwrap(),
+            "--",
+            "serve",
+        ]))
+        .unwrap();
+
+        assert_eq!(cli.run_name, None);
+    }
+}
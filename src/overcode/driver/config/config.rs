@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
     use crate::config::Config;
 
@@ -63,5 +62,102 @@ testcase = "test"
         assert!(result.is_err());
     }
 
-}
+    #[test]
+    fn test_init_gitignore_creates_new_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore_path = temp_dir.path().join(".gitignore");
+
+        Config::init_gitignore(temp_dir.path()).unwrap();
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "/.overcode/\n");
+    }
+
+    #[test]
+    fn test_init_gitignore_appends_missing_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore_path = temp_dir.path().join(".gitignore");
+        fs::write(&gitignore_path, "/target").unwrap();
+
+        Config::init_gitignore(temp_dir.path()).unwrap();
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "/target\n/.overcode/\n");
+    }
+
+    #[test]
+    fn test_init_gitignore_keeps_existing_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore_path = temp_dir.path().join(".gitignore");
+        fs::write(&gitignore_path, "/target\n.overcode\n").unwrap();
+
+        Config::init_gitignore(temp_dir.path()).unwrap();
+
+        let content = fs::read_to_string(&gitignore_path).unwrap();
+        assert_eq!(content, "/target\n.overcode\n");
+    }
 
+    #[test]
+    fn test_execute_loads_config_once() {
+        use std::cell::Cell;
+        use std::path::Path;
+        use crate::cli::Cli;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "true"
+args = []
+"#).unwrap();
+
+        let cli = Cli::parse_from(vec![
+            "overcode".to_string(),
+            "test".to_string(),
+            "--no-podman".to_string(),
+            "--config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]).unwrap();
+
+        let loads = Cell::new(0);
+        let loader = |path: &Path| {
+            loads.set(loads.get() + 1);
+            Config::load(path)
+        };
+
+        let result = crate::overcode::execute(&cli, &loader);
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(loads.get(), 1);
+    }
+
+    #[test]
+    fn test_execute_no_init_leaves_read_only_dir_untouched() {
+        use std::os::unix::fs::PermissionsExt;
+        use crate::cli::Cli;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("ci.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "true"
+args = []
+"#).unwrap();
+
+        let cli = Cli::parse_from(vec![
+            "overcode".to_string(),
+            "test".to_string(),
+            "--no-init".to_string(),
+            "--no-podman".to_string(),
+            "--config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]).unwrap();
+        assert!(cli.no_init);
+
+        fs::set_permissions(temp_dir.path(), fs::Permissions::from_mode(0o555)).unwrap();
+        let result = crate::overcode::execute(&cli, &Config::load);
+        fs::set_permissions(temp_dir.path(), fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(!temp_dir.path().join("overcode.toml").exists());
+    }
+}
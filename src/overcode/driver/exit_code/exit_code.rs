@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use crate::cli::Cli;
+    use crate::config::Config;
+    use crate::overcode::{
+        execute, OvercodeError, CONFIG_ERROR_EXIT_CODE, ENVIRONMENT_ERROR_EXIT_CODE,
+        INTERNAL_ERROR_EXIT_CODE, TEST_FAILURE_EXIT_CODE,
+    };
+    use crate::run::{RunTimedOut, TIMEOUT_EXIT_CODE};
+
+    fn execute_with(command: &str, toml_content: &str) -> Result<(), OvercodeError> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        let cli = Cli::parse_from(vec![
+            "overcode".to_string(),
+            command.to_string(),
+            "--no-init".to_string(),
+            "--no-podman".to_string(),
+            "--config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]).unwrap();
+        execute(&cli, &Config::load)
+    }
+
+    fn exit_code(result: Result<(), OvercodeError>) -> i32 {
+        result.unwrap_err().exit_code()
+    }
+
+    #[test]
+    fn test_failing_run_command_exits_with_its_own_code() {
+        let result = execute_with("run", r#"
+[command.run]
+command = "sh"
+args = ["-c", "exit 2"]
+"#);
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, OvercodeError::CommandExited(2, _)));
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_run_command_killed_by_signal_exits_with_test_failure() {
+        let result = execute_with("run", r#"
+[command.run]
+command = "sh"
+args = ["-c", "kill -9 $$"]
+"#);
+
+        assert_eq!(exit_code(result), TEST_FAILURE_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_failing_driver_exits_with_test_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::create_dir_all(temp_dir.path().join("tests")).unwrap();
+        fs::write(temp_dir.path().join("tests/a.sh"), "").unwrap();
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "tests/(.+)\\.sh"
+testcase = "$1"
+
+[command.test]
+command = "sh"
+args = ["{driver_file}"]
+"#).unwrap();
+        let cli = Cli::parse_from(vec![
+            "overcode".to_string(),
+            "test".to_string(),
+            "--no-podman".to_string(),
+            "--config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]).unwrap();
+
+        let result = execute(&cli, &Config::load);
+
+        assert_eq!(exit_code(result), TEST_FAILURE_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_invalid_config_exits_with_config_error() {
+        let result = execute_with("test", "[command.test\ncommand = ");
+
+        assert_eq!(exit_code(result), CONFIG_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_missing_section_exits_with_config_error() {
+        let result = execute_with("run", r#"
+[command.test]
+command = "true"
+args = []
+"#);
+
+        assert_eq!(exit_code(result), CONFIG_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_missing_image_exits_with_config_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "true"
+args = []
+"#).unwrap();
+        let cli = Cli::parse_from(vec![
+            "overcode".to_string(),
+            "test".to_string(),
+            "--no-init".to_string(),
+            "--config".to_string(),
+            config_path.to_string_lossy().to_string(),
+        ]).unwrap();
+
+        let err = execute(&cli, &Config::load).unwrap_err();
+
+        assert_eq!(err.exit_code(), CONFIG_ERROR_EXIT_CODE);
+        assert!(format!("{:?}", err).contains("command.test.image is required unless --no-podman is used"));
+    }
+
+    #[test]
+    fn test_gc_without_options_exits_with_config_error() {
+        let result = execute_with("gc", "");
+
+        assert_eq!(exit_code(result), CONFIG_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_unavailable_image_exits_with_environment_error() {
+        let result = execute_with("test", r#"
+[command.test]
+image = "localhost/overcode-missing-image:never"
+command = "true"
+args = []
+"#);
+
+        assert_eq!(exit_code(result), ENVIRONMENT_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_unclassified_error_exits_with_internal_error() {
+        let err = OvercodeError::from(anyhow::anyhow!("unexpected"));
+
+        assert_eq!(err.exit_code(), INTERNAL_ERROR_EXIT_CODE);
+    }
+
+    #[test]
+    fn test_timeout_keeps_its_exit_code_through_context() {
+        let err = anyhow::Error::new(RunTimedOut { timeout: Duration::from_secs(1) }).context("while running");
+
+        assert_eq!(OvercodeError::from(err).exit_code(), TIMEOUT_EXIT_CODE);
+    }
+}
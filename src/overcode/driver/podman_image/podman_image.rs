@@ -1,7 +1,6 @@
 #[cfg(test)]
 mod tests {
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
     use crate::podman_image::ensure_images;
     use crate::config::Config;
@@ -15,7 +14,7 @@ mod tests {
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = ensure_images(&config_path);
+        let result = ensure_images(&Config::load(&config_path).unwrap(), false);
         
         assert!(result.is_ok());
     }
@@ -33,7 +32,7 @@ args = ["test"]
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = ensure_images(&config_path);
+        let result = ensure_images(&Config::load(&config_path).unwrap(), false);
         
         if let Err(e) = &result {
             let error_msg = e.to_string();
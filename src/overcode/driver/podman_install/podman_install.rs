@@ -22,12 +22,8 @@ mod tests {
             .arg("--version")
             .output();
         
-        match output {
-            Ok(result) => {
-                assert!(result.status.code().is_some() || !result.status.success());
-            }
-            Err(_) => {
-            }
+        if let Ok(result) = output {
+            assert!(result.status.code().is_some() || !result.status.success());
         }
     }
 }
@@ -1,16 +1,23 @@
 #[cfg(test)]
 mod tests {
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
-    use crate::run::process_run;
+    use std::path::Path;
+    use std::time::Duration;
+    use crate::config::Config;
+    use crate::run::{RunOptions, RunTimedOut};
+
+    fn process_run(config_path: &Path, extra_args: &[String], options: &RunOptions) -> anyhow::Result<()> {
+        let config = Config::load(config_path)?;
+        crate::run::process_run(&config, config_path.parent().unwrap(), extra_args, options)
+    }
 
     #[test]
     fn test_process_run_without_config() {
         let temp_dir = TempDir::new().unwrap();
         let config_path = temp_dir.path().join("overcode.toml");
         
-        let result = process_run(&config_path, &[]);
+        let result = process_run(&config_path, &[], &RunOptions::default());
         
         assert!(result.is_err());
         let error_msg = result.unwrap_err().to_string();
@@ -28,7 +35,7 @@ file = ".gitignore"
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = process_run(&config_path, &[]);
+        let result = process_run(&config_path, &[], &RunOptions::default());
         
         assert!(result.is_err());
     }
@@ -45,7 +52,7 @@ args = ["hello"]
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = process_run(&config_path, &[]);
+        let result = process_run(&config_path, &[], &RunOptions::default());
         if let Err(e) = &result {
             let error_msg = e.to_string();
             assert!(!error_msg.contains("Failed to read config") && 
@@ -68,7 +75,7 @@ args = ["hello"]
         
         let extra_args = vec!["world".to_string(), "test".to_string()];
         
-        let result = process_run(&config_path, &extra_args);
+        let result = process_run(&config_path, &extra_args, &RunOptions::default());
         if let Err(e) = &result {
             let error_msg = e.to_string();
             assert!(!error_msg.contains("Failed to read config") && 
@@ -76,5 +83,26 @@ args = ["hello"]
                     !error_msg.contains("section not found"));
         }
     }
-}
 
+    #[test]
+    fn test_process_run_timeout_kills_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.run]
+command = "sleep"
+args = ["10"]
+timeout_secs = 60
+"#).unwrap();
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..RunOptions::default()
+        };
+        
+        let result = process_run(&config_path, &[], &options);
+        
+        let err = result.unwrap_err();
+        assert!(err.downcast_ref::<RunTimedOut>().is_some());
+        assert!(err.to_string().contains("timed out"));
+    }
+}
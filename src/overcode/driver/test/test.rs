@@ -1,16 +1,22 @@
 #[cfg(test)]
 mod tests {
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
-    use crate::test::process_test;
+    use std::path::Path;
+    use crate::config::Config;
+    use crate::test::TestOptions;
+
+    fn process_test(config_path: &Path, options: &TestOptions) -> anyhow::Result<()> {
+        let config = Config::load(config_path)?;
+        crate::test::process_test(&config, config_path.parent().unwrap(), options)
+    }
 
     #[test]
     fn test_process_test_without_config() {
         let temp_dir = TempDir::new().unwrap();
         let config_path = temp_dir.path().join("overcode.toml");
         
-        let result = process_test(&config_path);
+        let result = process_test(&config_path, &TestOptions::default());
         
         assert!(result.is_err());
         let error_msg = result.unwrap_err().to_string();
@@ -28,7 +34,7 @@ file = ".gitignore"
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = process_test(&config_path);
+        let result = process_test(&config_path, &TestOptions::default());
         
         assert!(result.is_err());
     }
@@ -46,7 +52,7 @@ image = "docker.io/library/rust:latest"
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = process_test(&config_path);
+        let result = process_test(&config_path, &TestOptions::default());
         
         assert!(result.is_ok());
     }
@@ -68,7 +74,7 @@ image = "docker.io/library/rust:latest"
 "#;
         fs::write(&config_path, toml_content).unwrap();
         
-        let result = process_test(&config_path);
+        let result = process_test(&config_path, &TestOptions::default());
         assert!(result.is_ok());
     }
 
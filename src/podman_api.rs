@@ -0,0 +1,286 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use log::{debug, info, warn};
+use crate::config::PodmanConfig;
+use crate::podman_image::{self, ImageTransport};
+
+const API_PREFIX: &str = "/v4.0.0/libpod";
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub fn socket_path(config: Option<&PodmanConfig>) -> Option<PathBuf> {
+    if config.and_then(|c| c.api) == Some(false) {
+        return None;
+    }
+    if let Some(socket) = config.and_then(|c| c.socket.as_deref()) {
+        return socket.strip_prefix("unix://").map(PathBuf::from);
+    }
+    if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
+        return Some(Path::new(&runtime_dir).join("podman/podman.sock"));
+    }
+    let uid = std::fs::metadata("/proc/self").ok()?.uid();
+    Some(PathBuf::from(format!("/run/user/{}/podman/podman.sock", uid)))
+}
+
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~:/@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: Box<dyn BufRead>,
+}
+
+impl Response {
+    fn text(mut self) -> Result<String> {
+        let mut body = String::new();
+        self.body.read_to_string(&mut body).context("Failed to read podman API response")?;
+        Ok(body)
+    }
+}
+
+struct ChunkedReader<R> {
+    inner: R,
+    remaining: usize,
+    done: bool,
+}
+
+impl<R: BufRead> ChunkedReader<R> {
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        self.inner.read_line(&mut line)?;
+        Ok(line.trim_end().to_string())
+    }
+}
+
+impl<R: BufRead> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.done || buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            let line = self.read_line()?;
+            let size = line.split(';').next().unwrap_or_default().trim();
+            self.remaining = usize::from_str_radix(size, 16)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("invalid chunk size: {:?}", line)))?;
+            if self.remaining == 0 {
+                while !self.read_line()?.is_empty() {}
+                self.done = true;
+                return Ok(0);
+            }
+        }
+        let limit = buf.len().min(self.remaining);
+        let read = self.inner.read(&mut buf[..limit])?;
+        if read == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "podman API response ended mid-chunk"));
+        }
+        self.remaining -= read;
+        if self.remaining == 0 {
+            self.read_line()?;
+        }
+        Ok(read)
+    }
+}
+
+pub struct ApiClient {
+    socket: PathBuf,
+}
+
+impl ApiClient {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self { socket: socket.into() }
+    }
+
+    fn request(&self, method: &str, path: &str, timeout: Option<Duration>) -> Result<Response> {
+        let mut stream = UnixStream::connect(&self.socket)
+            .with_context(|| format!("Failed to connect to podman API socket: {:?}", self.socket))?;
+        stream.set_read_timeout(timeout).ok();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: d\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            method, path
+        )
+        .context("Failed to send podman API request")?;
+
+        let mut reader = BufReader::new(stream);
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line).context("Failed to read podman API response")?;
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .with_context(|| format!("Invalid podman API status line: {:?}", status_line.trim_end()))?;
+
+        let mut chunked = false;
+        let mut content_length = None;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).context("Failed to read podman API response headers")?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let Some((name, value)) = header.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            if name.eq_ignore_ascii_case("transfer-encoding") {
+                chunked = value.eq_ignore_ascii_case("chunked");
+            } else if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse::<u64>().ok();
+            }
+        }
+
+        let body: Box<dyn BufRead> = if chunked {
+            Box::new(BufReader::new(ChunkedReader { inner: reader, remaining: 0, done: false }))
+        } else if let Some(length) = content_length {
+            Box::new(reader.take(length))
+        } else {
+            Box::new(reader)
+        };
+        Ok(Response { status, body })
+    }
+
+    pub fn ping(&self) -> bool {
+        match self.request("GET", "/_ping", Some(PING_TIMEOUT)) {
+            Ok(response) => response.status == 200,
+            Err(err) => {
+                debug!("podman API is not available: {:#}", err);
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ImageInspect {
+    #[serde(rename = "Digest", default)]
+    digest: Option<String>,
+    #[serde(rename = "RepoDigests", default)]
+    repo_digests: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PullEvent {
+    #[serde(default)]
+    stream: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: String,
+}
+
+fn error_message(response: Response) -> String {
+    let status = response.status;
+    let body = response.text().unwrap_or_default();
+    match serde_json::from_str::<ErrorBody>(&body) {
+        Ok(error) if !error.message.is_empty() => format!("{} (HTTP {})", error.message, status),
+        _ => format!("HTTP {}: {}", status, body.trim()),
+    }
+}
+
+pub struct ApiTransport {
+    client: ApiClient,
+}
+
+impl ApiTransport {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    pub fn connect(config: Option<&PodmanConfig>) -> Option<Self> {
+        let socket = socket_path(config)?;
+        if !socket.exists() {
+            return None;
+        }
+        let client = ApiClient::new(socket);
+        client.ping().then(|| Self::new(client))
+    }
+}
+
+impl ImageTransport for ApiTransport {
+    fn exists(&self, image: &str) -> bool {
+        let path = format!("{}/images/{}/exists", API_PREFIX, encode(image));
+        match self.client.request("GET", &path, None) {
+            Ok(response) => response.status == 204,
+            Err(err) => {
+                warn!("podman API image exists failed for {}: {:#}", image, err);
+                false
+            }
+        }
+    }
+
+    fn local_digests(&self, image: &str) -> Vec<String> {
+        let path = format!("{}/images/{}/json", API_PREFIX, encode(image));
+        let inspect = self.client
+            .request("GET", &path, None)
+            .and_then(|response| {
+                if response.status != 200 {
+                    bail!(error_message(response));
+                }
+                Ok(serde_json::from_str::<ImageInspect>(&response.text()?)?)
+            });
+        match inspect {
+            Ok(inspect) => podman_image::parse_digests(
+                inspect.digest.iter().chain(inspect.repo_digests.iter()).map(String::as_str),
+            ),
+            Err(err) => {
+                debug!("podman API image inspect failed for {}: {:#}", image, err);
+                Vec::new()
+            }
+        }
+    }
+
+    fn pull(&self, image: &str) -> Result<()> {
+        info!("Pulling image: {}", image);
+
+        let path = format!("{}/images/pull?reference={}", API_PREFIX, encode(image));
+        let response = self.client
+            .request("POST", &path, None)
+            .with_context(|| format!("Failed to pull image: {}", image))?;
+        if response.status != 200 {
+            bail!("Failed to pull image: {}. {}", image, error_message(response));
+        }
+
+        for line in response.body.lines() {
+            let line = line.with_context(|| format!("Failed to read pull progress for image: {}", image))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: PullEvent = serde_json::from_str(&line)
+                .with_context(|| format!("Invalid pull progress event: {}", line))?;
+            if let Some(error) = event.error {
+                bail!("Failed to pull image: {}. {}", image, error.trim());
+            }
+            if let Some(stream) = event.stream {
+                let stream = stream.trim();
+                if !stream.is_empty() {
+                    info!("{}: {}", image, stream);
+                }
+            }
+        }
+
+        info!("Successfully pulled image: {}", image);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "podman_api/driver/transport/transport.rs"]
+mod driver_transport_transport;
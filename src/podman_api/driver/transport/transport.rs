@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+    use crate::config::PodmanConfig;
+    use crate::podman_api::{socket_path, ApiClient, ApiTransport};
+    use crate::podman_image::ImageTransport;
+
+    struct FakeServer {
+        _dir: TempDir,
+        socket: PathBuf,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    fn fake_server(respond: fn(&str) -> String) -> FakeServer {
+        let dir = TempDir::new().unwrap();
+        let socket = dir.path().join("podman.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim_end().is_empty() {
+                        break;
+                    }
+                }
+                let request_line = request_line.trim_end().to_string();
+                stream.write_all(respond(&request_line).as_bytes()).unwrap();
+                recorded.lock().unwrap().push(request_line);
+            }
+        });
+        FakeServer { _dir: dir, socket, requests }
+    }
+
+    fn transport(server: &FakeServer) -> ApiTransport {
+        ApiTransport::new(ApiClient::new(&server.socket))
+    }
+
+    fn respond_json(status: &str, body: &str) -> String {
+        format!("HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body)
+    }
+
+    fn respond_chunked(lines: &[&str]) -> String {
+        let mut response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_string();
+        for line in lines {
+            let chunk = format!("{}\n", line);
+            response.push_str(&format!("{:x}\r\n{}\r\n", chunk.len(), chunk));
+        }
+        response.push_str("0\r\n\r\n");
+        response
+    }
+
+    #[test]
+    fn test_exists_maps_status_codes() {
+        let server = fake_server(|request| {
+            if request.contains("/rust:latest/") {
+                "HTTP/1.1 204 No Content\r\n\r\n".to_string()
+            } else {
+                respond_json("404 Not Found", r#"{"cause":"image not known","message":"missing: image not known","response":404}"#)
+            }
+        });
+        let api = transport(&server);
+
+        assert!(api.exists("docker.io/library/rust:latest"));
+        assert!(!api.exists("missing:1"));
+        assert_eq!(
+            server.requests.lock().unwrap()[0],
+            "GET /v4.0.0/libpod/images/docker.io/library/rust:latest/exists HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_local_digests_reads_inspect_response() {
+        let server = fake_server(|_| respond_json(
+            "200 OK",
+            r#"{"Id":"abc","Digest":"sha256:1111","RepoDigests":["docker.io/library/rust@sha256:2222"]}"#,
+        ));
+
+        assert_eq!(transport(&server).local_digests("rust:latest"), vec!["sha256:1111", "sha256:2222"]);
+    }
+
+    #[test]
+    fn test_pull_streams_progress_until_completion() {
+        let server = fake_server(|_| respond_chunked(&[
+            r#"{"stream":"Trying to pull docker.io/library/rust:latest...\n"}"#,
+            r#"{"stream":"Copying blob sha256:aaaa\n"}"#,
+            r#"{"images":["abc"],"id":"abc"}"#,
+        ]));
+
+        transport(&server).pull("rust:latest").unwrap();
+
+        assert_eq!(
+            server.requests.lock().unwrap()[0],
+            "POST /v4.0.0/libpod/images/pull?reference=rust:latest HTTP/1.1"
+        );
+    }
+
+    #[test]
+    fn test_pull_reports_error_event() {
+        let server = fake_server(|_| respond_chunked(&[
+            r#"{"stream":"Trying to pull localhost/nope:1...\n"}"#,
+            r#"{"error":"initializing source docker://localhost/nope:1: connection refused"}"#,
+        ]));
+
+        let err = transport(&server).pull("localhost/nope:1").unwrap_err();
+
+        assert!(err.to_string().contains("connection refused"), "{}", err);
+    }
+
+    #[test]
+    fn test_pull_reports_http_error() {
+        let server = fake_server(|_| respond_json("500 Internal Server Error", r#"{"message":"storage is locked"}"#));
+
+        let err = transport(&server).pull("rust:latest").unwrap_err();
+
+        assert!(err.to_string().contains("storage is locked (HTTP 500)"), "{}", err);
+    }
+
+    #[test]
+    fn test_connect_pings_the_socket() {
+        let server = fake_server(|request| {
+            if request.starts_with("GET /_ping ") {
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nOK".to_string()
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+            }
+        });
+        let config = PodmanConfig {
+            socket: Some(format!("unix://{}", server.socket.display())),
+            ..PodmanConfig::default()
+        };
+
+        assert!(ApiTransport::connect(Some(&config)).is_some());
+    }
+
+    #[test]
+    fn test_socket_path_falls_back_to_cli() {
+        let missing = PodmanConfig { socket: Some("unix:///nonexistent/podman.sock".to_string()), ..PodmanConfig::default() };
+        let remote = PodmanConfig { socket: Some("ssh://core@127.0.0.1:2222/run/podman/podman.sock".to_string()), ..PodmanConfig::default() };
+        let connection = PodmanConfig { socket: Some("podman-machine-default".to_string()), ..PodmanConfig::default() };
+        let disabled = PodmanConfig { api: Some(false), ..PodmanConfig::default() };
+
+        assert_eq!(socket_path(Some(&missing)), Some(PathBuf::from("/nonexistent/podman.sock")));
+        assert!(ApiTransport::connect(Some(&missing)).is_none());
+        assert_eq!(socket_path(Some(&remote)), None);
+        assert_eq!(socket_path(Some(&connection)), None);
+        assert_eq!(socket_path(Some(&disabled)), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io;
+use std::process::{Command, Output};
+use std::sync::Mutex;
+use log::warn;
+use crate::config::PodmanConfig;
+use crate::podman_machine;
+
+const MACHINE_NOT_RUNNING_PATTERNS: &[&str] = &[
+    "Cannot connect to Podman",
+    "unable to connect to Podman socket",
+    "podman machine start",
+];
+
+#[derive(Debug)]
+pub struct EngineUnavailable(pub String);
+
+impl fmt::Display for EngineUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for EngineUnavailable {}
+
+static GLOBAL_ARGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn build_global_args(config: &PodmanConfig) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(ref socket) = config.socket {
+        if socket.contains("://") {
+            args.push("--url".to_string());
+        } else {
+            args.push("--connection".to_string());
+        }
+        args.push(socket.clone());
+    }
+    if let Some(timeout_secs) = config.timeout_secs {
+        args.push("--timeout".to_string());
+        args.push(timeout_secs.to_string());
+    }
+    if let Some(ref log_level) = config.log_level {
+        args.push("--log-level".to_string());
+        args.push(log_level.clone());
+    }
+
+    args
+}
+
+pub fn configure(config: Option<&PodmanConfig>) {
+    let args = config.map(build_global_args).unwrap_or_default();
+    *GLOBAL_ARGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = args;
+}
+
+pub fn podman() -> Command {
+    let mut command = Command::new("podman");
+    command.args(GLOBAL_ARGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).iter());
+    command
+}
+
+fn diagnose_engine_probe(probe: io::Result<Output>, path: Option<&str>) -> Result<(), EngineUnavailable> {
+    let output = match probe {
+        Ok(output) => output,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(EngineUnavailable(format!(
+                "podman not found in PATH; run `overcode init` or install podman/docker (searched PATH: {})",
+                path.unwrap_or_default()
+            )));
+        }
+        Err(e) => {
+            warn!("Failed to probe podman: {}", e);
+            return Ok(());
+        }
+    };
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if MACHINE_NOT_RUNNING_PATTERNS.iter().any(|pattern| stderr.contains(pattern)) {
+        return Err(EngineUnavailable(format!(
+            "podman is installed but cannot reach its engine; if you use podman machine, run `podman machine start`\n{}",
+            stderr.trim()
+        )));
+    }
+    warn!("podman info failed: {}", stderr.trim());
+    Ok(())
+}
+
+pub fn ensure_engine(config: Option<&PodmanConfig>) -> anyhow::Result<()> {
+    podman_machine::ensure_machine(config)?;
+    let probe = podman().args(["info", "--format", "{{.Host.Arch}}"]).output();
+    diagnose_engine_probe(probe, std::env::var("PATH").ok().as_deref())?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "podman_command/driver/config/config.rs"]
+mod driver_config_config;
+
+#[cfg(test)]
+#[path = "podman_command/driver/engine/engine.rs"]
+mod driver_engine_engine;
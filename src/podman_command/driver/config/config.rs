@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{Config, PodmanConfig};
+    use crate::podman_command::build_global_args;
+
+    #[test]
+    fn test_podman_section_is_optional() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, "").unwrap();
+
+        let config = Config::load(&config_path).unwrap();
+
+        assert!(config.podman.is_none());
+    }
+
+    #[test]
+    fn test_podman_section_fields_are_parsed() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[podman]
+socket = "unix:///run/user/1000/podman/podman.sock"
+timeout_secs = 30
+log_level = "debug"
+"#).unwrap();
+
+        let podman = Config::load(&config_path).unwrap().podman.unwrap();
+
+        assert_eq!(podman.socket.as_deref(), Some("unix:///run/user/1000/podman/podman.sock"));
+        assert_eq!(podman.timeout_secs, Some(30));
+        assert_eq!(podman.log_level.as_deref(), Some("debug"));
+    }
+
+    #[test]
+    fn test_global_args_empty_by_default() {
+        assert!(build_global_args(&PodmanConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_global_args_socket_url() {
+        let config = PodmanConfig {
+            socket: Some("unix:///run/user/1000/podman/podman.sock".to_string()),
+            ..PodmanConfig::default()
+        };
+
+        assert_eq!(
+            build_global_args(&config),
+            vec!["--url", "unix:///run/user/1000/podman/podman.sock"]
+        );
+    }
+
+    #[test]
+    fn test_global_args_named_connection() {
+        let config = PodmanConfig {
+            socket: Some("rootless".to_string()),
+            ..PodmanConfig::default()
+        };
+
+        assert_eq!(build_global_args(&config), vec!["--connection", "rootless"]);
+    }
+
+    #[test]
+    fn test_global_args_timeout() {
+        let config = PodmanConfig {
+            timeout_secs: Some(30),
+            ..PodmanConfig::default()
+        };
+
+        assert_eq!(build_global_args(&config), vec!["--timeout", "30"]);
+    }
+
+    #[test]
+    fn test_global_args_log_level() {
+        let config = PodmanConfig {
+            log_level: Some("debug".to_string()),
+            ..PodmanConfig::default()
+        };
+
+        assert_eq!(build_global_args(&config), vec!["--log-level", "debug"]);
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{ExitStatus, Output};
+    use crate::podman_command::diagnose_engine_probe;
+
+    fn output(code: i32, stderr: &str) -> io::Result<Output> {
+        Ok(Output {
+            status: ExitStatus::from_raw(code << 8),
+            stdout: Vec::new(),
+            stderr: stderr.as_bytes().to_vec(),
+        })
+    }
+
+    #[test]
+    fn test_missing_binary_reports_searched_path() {
+        let probe = Err(io::Error::from(io::ErrorKind::NotFound));
+
+        let err = diagnose_engine_probe(probe, Some("/usr/bin:/bin")).unwrap_err();
+
+        assert!(err.to_string().contains("podman not found in PATH"));
+        assert!(err.to_string().contains("overcode init"));
+        assert!(err.to_string().contains("/usr/bin:/bin"));
+    }
+
+    #[test]
+    fn test_stopped_machine_suggests_machine_start() {
+        let probe = output(125, "Error: Cannot connect to Podman. Please verify your connection to the Linux system using `podman system connection list`, or try `podman machine init` and `podman machine start` to manage a new Linux VM\n");
+
+        let err = diagnose_engine_probe(probe, None).unwrap_err();
+
+        assert!(err.to_string().contains("run `podman machine start`"));
+        assert!(err.to_string().contains("Cannot connect to Podman"));
+    }
+
+    #[test]
+    fn test_running_engine_passes() {
+        assert!(diagnose_engine_probe(output(0, ""), None).is_ok());
+    }
+
+    #[test]
+    fn test_other_probe_failures_are_not_fatal() {
+        assert!(diagnose_engine_probe(output(1, "Error: some unrelated warning"), None).is_ok());
+        assert!(diagnose_engine_probe(Err(io::Error::from(io::ErrorKind::PermissionDenied)), None).is_ok());
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{Context, Result, bail};
+use std::sync::{Mutex, Once};
+use std::time::{Duration, Instant};
+use log::{info, warn};
+use crate::podman_command;
+
+static ACTIVE_CONTAINERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static INTERRUPT_HANDLER: Once = Once::new();
+
+fn build_start_args(image: &str, base_args: &[String]) -> Vec<String> {
+    let mut args = vec![
+        "run".to_string(),
+        "-d".to_string(),
+        "--rm".to_string(),
+    ];
+    args.extend_from_slice(base_args);
+    args.extend([
+        image.to_string(),
+        "sleep".to_string(),
+        "infinity".to_string(),
+    ]);
+    args
+}
+
+fn build_stop_args(container_id: &str) -> Vec<String> {
+    vec![
+        "stop".to_string(),
+        "-t".to_string(),
+        "0".to_string(),
+        container_id.to_string(),
+    ]
+}
+
+fn stop_container(container_id: &str) {
+    let result = podman_command::podman()
+        .args(build_stop_args(container_id))
+        .output();
+    match result {
+        Ok(output) if output.status.success() => info!("Stopped shared container: {}", container_id),
+        Ok(output) => warn!("Failed to stop shared container {}: {}", container_id, String::from_utf8_lossy(&output.stderr).trim()),
+        Err(e) => warn!("Failed to stop shared container {}: {}", container_id, e),
+    }
+}
+
+fn install_interrupt_handler() {
+    INTERRUPT_HANDLER.call_once(|| {
+        let result = ctrlc::set_handler(|| {
+            let containers = ACTIVE_CONTAINERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+            for container_id in &containers {
+                stop_container(container_id);
+            }
+            std::process::exit(130);
+        });
+        if let Err(e) = result {
+            warn!("Failed to install interrupt handler for shared containers: {}", e);
+        }
+    });
+}
+
+pub struct SharedContainer {
+    id: String,
+    startup: Duration,
+}
+
+impl SharedContainer {
+    pub fn start(image: &str, base_args: &[String]) -> Result<Self> {
+        install_interrupt_handler();
+
+        info!("Starting shared container (image: {})", image);
+        let started_at = Instant::now();
+        let output = podman_command::podman()
+            .args(build_start_args(image, base_args))
+            .output()
+            .with_context(|| format!("Failed to start shared container for image: {}", image))?;
+        let startup = started_at.elapsed();
+
+        if !output.status.success() {
+            bail!(
+                "Failed to start shared container for image: {}: {}",
+                image,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        ACTIVE_CONTAINERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(id.clone());
+        info!("Shared container {} started in {:.2}s", id, startup.as_secs_f64());
+
+        Ok(Self { id, startup })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn startup(&self) -> Duration {
+        self.startup
+    }
+}
+
+impl Drop for SharedContainer {
+    fn drop(&mut self) {
+        stop_container(&self.id);
+        ACTIVE_CONTAINERS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|container_id| container_id != &self.id);
+    }
+}
+
+pub fn format_startup_savings(startup: Duration, reused: usize) -> String {
+    let saved = startup.mul_f64(reused.saturating_sub(1) as f64);
+    format!(
+        "Shared container: {} driver(s) reused one container (startup {:.2}s), saving ~{:.2}s",
+        reused,
+        startup.as_secs_f64(),
+        saved.as_secs_f64()
+    )
+}
+
+#[cfg(test)]
+#[path = "podman_container/driver/args/args.rs"]
+mod driver_args_args;
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::podman_container::{build_start_args, build_stop_args, format_startup_savings};
+
+    #[test]
+    fn test_build_start_args_runs_detached_sleep() {
+        let base_args = vec!["-v".to_string(), "/repo:/repo".to_string()];
+
+        let args = build_start_args("docker.io/library/rust:latest", &base_args);
+
+        assert_eq!(
+            args,
+            vec![
+                "run", "-d", "--rm",
+                "-v", "/repo:/repo",
+                "docker.io/library/rust:latest",
+                "sleep", "infinity",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_stop_args_stops_immediately() {
+        assert_eq!(build_stop_args("abc123"), vec!["stop", "-t", "0", "abc123"]);
+    }
+
+    #[test]
+    fn test_format_startup_savings_counts_avoided_starts() {
+        let summary = format_startup_savings(Duration::from_millis(2_500), 5);
+
+        assert_eq!(
+            summary,
+            "Shared container: 5 driver(s) reused one container (startup 2.50s), saving ~10.00s"
+        );
+    }
+
+    #[test]
+    fn test_format_startup_savings_without_reuse() {
+        let summary = format_startup_savings(Duration::from_secs(3), 0);
+
+        assert!(summary.ends_with("saving ~0.00s"));
+    }
+}
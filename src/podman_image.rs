@@ -1,25 +1,94 @@
-use std::path::Path;
-use std::process::Command;
 use std::collections::HashSet;
-use log::{info, warn};
-use crate::config;
+use std::process::Command;
+use log::{debug, info, warn};
+use crate::config::{self, PodmanConfig};
+use crate::podman_api::ApiTransport;
+use crate::podman_command;
 use crate::podman_image_download;
+use crate::scheduler::{self, Semaphore};
 use anyhow::Result;
 
-fn image_exists(image: &str) -> bool {
-    let output = Command::new("podman")
-        .args(&["image", "exists", image])
+pub trait ImageTransport: Sync {
+    fn exists(&self, image: &str) -> bool;
+    fn local_digests(&self, image: &str) -> Vec<String>;
+    fn pull(&self, image: &str) -> Result<()>;
+}
+
+pub struct CliTransport;
+
+impl ImageTransport for CliTransport {
+    fn exists(&self, image: &str) -> bool {
+        let output = podman_command::podman()
+            .args(["image", "exists", image])
+            .output();
+        
+        match output {
+            Ok(result) => result.status.success(),
+            Err(_) => false,
+        }
+    }
+
+    fn local_digests(&self, image: &str) -> Vec<String> {
+        let output = podman_command::podman()
+            .args(["image", "inspect", "--format", "{{.Digest}}\n{{range .RepoDigests}}{{.}}\n{{end}}", image])
+            .output();
+
+        match output {
+            Ok(result) if result.status.success() => parse_digests(String::from_utf8_lossy(&result.stdout).lines()),
+            _ => Vec::new(),
+        }
+    }
+
+    fn pull(&self, image: &str) -> Result<()> {
+        podman_image_download::pull_image(image)
+    }
+}
+
+pub fn parse_digests<'a>(references: impl Iterator<Item = &'a str>) -> Vec<String> {
+    references
+        .filter_map(|reference| reference.rsplit('@').next())
+        .filter(|digest| !digest.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+pub fn transport(podman: Option<&PodmanConfig>) -> Box<dyn ImageTransport> {
+    match ApiTransport::connect(podman) {
+        Some(api) => {
+            debug!("Using the podman API socket for image checks");
+            Box::new(api)
+        }
+        None => Box::new(CliTransport),
+    }
+}
+
+fn remote_digest(image: &str) -> Option<String> {
+    let output = Command::new("skopeo")
+        .args(["inspect", "--format", "{{.Digest}}", &format!("docker://{}", image)])
         .output();
-    
+
     match output {
-        Ok(result) => result.status.success(),
-        Err(_) => false,
+        Ok(result) if result.status.success() => {
+            let digest = String::from_utf8_lossy(&result.stdout).trim().to_string();
+            (!digest.is_empty()).then_some(digest)
+        }
+        _ => None,
     }
 }
 
-pub fn ensure_images(config_path: &Path) -> Result<()> {
-    let config = config::Config::load(config_path)?;
-    
+fn image_outdated_with(
+    image: &str,
+    local: &dyn Fn(&str) -> Vec<String>,
+    remote: &dyn Fn(&str) -> Option<String>,
+) -> bool {
+    let Some(remote) = remote(image) else {
+        warn!("Could not fetch remote digest for {}, keeping local image", image);
+        return false;
+    };
+    !local(image).contains(&remote)
+}
+
+pub fn collect_images(config: &config::Config) -> HashSet<String> {
     let mut images = HashSet::new();
     
     if let Some(command) = &config.command {
@@ -33,22 +102,98 @@ pub fn ensure_images(config_path: &Path) -> Result<()> {
                 images.insert(image.clone());
             }
         }
+        if let Some(bench_config) = &command.bench {
+            if let Some(image) = &bench_config.image {
+                images.insert(image.clone());
+            }
+        }
+        for custom_config in command.custom.values() {
+            if let Some(image) = &custom_config.image {
+                images.insert(image.clone());
+            }
+        }
     }
     
+    images
+}
+
+pub fn ensure_images(config: &config::Config, refresh: bool) -> Result<()> {
+    let images = collect_images(config);
+    
     if images.is_empty() {
         info!("No images specified in command.test or command.run");
         return Ok(());
     }
     
+    ensure_image_set(&images, config.podman.as_ref(), refresh)
+}
+
+pub fn ensure_image_set(images: &HashSet<String>, podman: Option<&PodmanConfig>, refresh: bool) -> Result<()> {
+    let transport = transport(podman);
+    let image_outdated = |image: &str| image_outdated_with(image, &|image| transport.local_digests(image), &remote_digest);
+    let outdated: Option<&(dyn Fn(&str) -> bool + Sync)> = if refresh { Some(&image_outdated) } else { None };
+    ensure_image_set_with(
+        images,
+        podman.and_then(|p| p.pull_concurrency),
+        &|image| transport.exists(image),
+        outdated,
+        &|image| transport.pull(image),
+    )
+}
+
+fn ensure_image_set_with(
+    images: &HashSet<String>,
+    pull_concurrency: Option<usize>,
+    exists: &(dyn Fn(&str) -> bool + Sync),
+    outdated: Option<&(dyn Fn(&str) -> bool + Sync)>,
+    pull: &(dyn Fn(&str) -> Result<()> + Sync),
+) -> Result<()> {
     info!("Checking {} image(s)...", images.len());
     
-    for image_name in &images {
-        if image_exists(image_name) {
-            info!("Image already exists: {}", image_name);
-        } else {
-            warn!("Image not found: {}, pulling...", image_name);
-            podman_image_download::pull_image(image_name)?;
-        }
+    let mut sorted: Vec<&String> = images.iter().collect();
+    sorted.sort();
+    let to_pull: Vec<&String> = sorted
+        .into_iter()
+        .filter(|image_name| {
+            if !exists(image_name) {
+                warn!("Image not found: {}, pulling...", image_name);
+                true
+            } else if outdated.is_some_and(|outdated| outdated(image_name)) {
+                warn!("Image digest changed upstream: {}, pulling...", image_name);
+                true
+            } else {
+                info!("Image already exists: {}", image_name);
+                false
+            }
+        })
+        .collect();
+    
+    let pull_slots = Semaphore::new(pull_concurrency.unwrap_or(to_pull.len()).max(1));
+    let failures: Vec<String> = std::thread::scope(|scope| {
+        let workers: Vec<_> = to_pull
+            .iter()
+            .map(|image_name| {
+                let pull_slots = &pull_slots;
+                scope.spawn(move || {
+                    let _pull_permit = pull_slots.acquire();
+                    let _permit = scheduler::acquire();
+                    pull(image_name)
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .zip(&to_pull)
+            .filter_map(|(worker, image_name)| {
+                let result = worker
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Image pull worker panicked")));
+                result.err().map(|err| format!("{}: {:#}", image_name, err))
+            })
+            .collect()
+    });
+    if !failures.is_empty() {
+        anyhow::bail!("Failed to pull {} image(s):\n  {}", failures.len(), failures.join("\n  "));
     }
     
     info!("All images are available");
@@ -65,4 +210,16 @@ mod driver_podman_image_download_fail;
 
 #[cfg(test)]
 #[path = "podman_image/driver/podman_image_download/success.rs"]
-mod driver_podman_image_download_success;
\ No newline at end of file
+mod driver_podman_image_download_success;
+
+#[cfg(test)]
+#[path = "podman_image/driver/pull/pull.rs"]
+mod driver_pull_pull;
+
+#[cfg(test)]
+#[path = "podman_image/driver/refresh/refresh.rs"]
+mod driver_refresh_refresh;
+
+#[cfg(test)]
+#[path = "podman_image/driver/transport/transport.rs"]
+mod driver_transport_transport;
\ No newline at end of file
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+    use crate::podman_image::ensure_image_set_with;
+
+    fn image_set(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pulls_only_missing_images_with_bounded_parallelism() {
+        let images = image_set(&["a:1", "b:1", "c:1", "d:1", "e:1", "present:1"]);
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let pulled = Mutex::new(Vec::new());
+        let pull = |image: &str| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(50));
+            running.fetch_sub(1, Ordering::SeqCst);
+            pulled.lock().unwrap().push(image.to_string());
+            Ok(())
+        };
+
+        ensure_image_set_with(&images, Some(2), &|image| image == "present:1", None, &pull).unwrap();
+
+        let mut pulled = pulled.into_inner().unwrap();
+        pulled.sort();
+        assert_eq!(pulled, vec!["a:1", "b:1", "c:1", "d:1", "e:1"]);
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn test_reports_every_failed_pull() {
+        let images = image_set(&["bad:1", "good:1", "worse:1"]);
+        let pull = |image: &str| {
+            if image == "good:1" {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("manifest unknown"))
+            }
+        };
+
+        let error_msg = ensure_image_set_with(&images, Some(1), &|_| false, None, &pull)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error_msg.contains("Failed to pull 2 image(s)"));
+        assert!(error_msg.contains("bad:1: manifest unknown"));
+        assert!(error_msg.contains("worse:1: manifest unknown"));
+        assert!(!error_msg.contains("good:1"));
+    }
+
+    #[test]
+    fn test_present_images_are_not_pulled() {
+        let images = image_set(&["a:1", "b:1"]);
+
+        ensure_image_set_with(&images, None, &|_| true, None, &|image| panic!("unexpected pull of {}", image)).unwrap();
+    }
+}
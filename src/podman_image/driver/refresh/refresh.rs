@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use crate::podman_image::{ensure_image_set_with, image_outdated_with};
+
+    const LOCAL: &str = "sha256:1111";
+    const REMOTE: &str = "sha256:2222";
+
+    fn local(_: &str) -> Vec<String> {
+        vec![LOCAL.to_string()]
+    }
+
+    #[test]
+    fn test_image_outdated_on_digest_mismatch() {
+        assert!(image_outdated_with("rust:latest", &local, &|_| Some(REMOTE.to_string())));
+    }
+
+    #[test]
+    fn test_image_up_to_date_when_remote_digest_is_local() {
+        let local = |_: &str| vec![REMOTE.to_string(), LOCAL.to_string()];
+
+        assert!(!image_outdated_with("rust:latest", &local, &|_| Some(LOCAL.to_string())));
+    }
+
+    #[test]
+    fn test_image_kept_when_remote_digest_unavailable() {
+        assert!(!image_outdated_with("rust:latest", &local, &|_| None));
+    }
+
+    #[test]
+    fn test_digest_mismatch_triggers_pull() {
+        let images: HashSet<String> = ["fresh:1", "moved:latest"].iter().map(|name| name.to_string()).collect();
+        let pulled = Mutex::new(Vec::new());
+        let outdated = |image: &str| {
+            let remote = |image: &str| Some(if image == "moved:latest" { REMOTE } else { LOCAL }.to_string());
+            image_outdated_with(image, &local, &remote)
+        };
+        let pull = |image: &str| {
+            pulled.lock().unwrap().push(image.to_string());
+            Ok(())
+        };
+
+        ensure_image_set_with(&images, None, &|_| true, Some(&outdated), &pull).unwrap();
+
+        assert_eq!(pulled.into_inner().unwrap(), vec!["moved:latest"]);
+    }
+}
@@ -0,0 +1,19 @@
+#[cfg(test)]
+mod tests {
+    use crate::podman_image::{parse_digests, CliTransport, ImageTransport};
+
+    #[test]
+    fn test_parse_digests_strips_repository() {
+        let output = "sha256:1111\ndocker.io/library/rust@sha256:2222\n\n";
+
+        assert_eq!(parse_digests(output.lines()), vec!["sha256:1111", "sha256:2222"]);
+    }
+
+    #[test]
+    fn test_cli_transport_reports_missing_image() {
+        let cli = CliTransport;
+
+        assert!(!cli.exists("localhost/overcode-missing-image:never"));
+        assert!(cli.local_digests("localhost/overcode-missing-image:never").is_empty());
+    }
+}
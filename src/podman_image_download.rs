@@ -1,12 +1,12 @@
 use anyhow::{Context, Result, bail};
-use std::process::Command;
 use log::info;
+use crate::podman_command;
 
 pub fn pull_image(image: &str) -> Result<()> {
     info!("Pulling image: {}", image);
     
-    let status = Command::new("podman")
-        .args(&["pull", image])
+    let status = podman_command::podman()
+        .args(["pull", image])
         .status()
         .with_context(|| format!("Failed to execute podman pull for image: {}", image))?;
     
@@ -1,28 +1,86 @@
 use anyhow::{Context, Result, bail};
+use serde::Serialize;
 use std::process::Command;
 use std::fs;
+use std::path::Path;
 use log::{info, warn};
 
-fn check_podman_installed() -> bool {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatusFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl StatusFormat {
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown output format: {}. Use 'human' or 'json'", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PodmanStatus {
+    AlreadyInstalled { version: String },
+    JustInstalled { version: String },
+    InstallFailed { reason: String },
+    UnsupportedOs,
+}
+
+impl PodmanStatus {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).context("Failed to serialize podman status")
+    }
+
+    pub fn into_result(self) -> Result<()> {
+        match self {
+            Self::AlreadyInstalled { .. } | Self::JustInstalled { .. } => Ok(()),
+            Self::InstallFailed { reason } => bail!("{}", reason),
+            Self::UnsupportedOs => {
+                warn!("{}", wsl2_instructions());
+                Ok(())
+            }
+        }
+    }
+}
+
+fn podman_version() -> Option<String> {
     let output = Command::new("podman")
         .arg("--version")
-        .output();
+        .output()
+        .ok()?;
     
-    match output {
-        Ok(result) => result.status.success(),
-        Err(_) => false,
+    if !output.status.success() {
+        return None;
     }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn is_wsl2(proc_version: &str) -> bool {
+    proc_version.to_lowercase().contains("microsoft") || proc_version.contains("WSL")
 }
 
 fn detect_os() -> Result<OsType> {
-    let os_release_path = "/etc/os-release";
+    detect_os_from(Path::new("/proc/version"), Path::new("/etc/os-release"))
+}
+
+fn detect_os_from(proc_version_path: &Path, os_release_path: &Path) -> Result<OsType> {
+    if let Ok(proc_version) = fs::read_to_string(proc_version_path) {
+        if is_wsl2(&proc_version) {
+            return Ok(OsType::WindowsWSL2);
+        }
+    }
     
-    if !std::path::Path::new(os_release_path).exists() {
-        bail!("Cannot detect OS: /etc/os-release not found");
+    if !os_release_path.exists() {
+        bail!("Cannot detect OS: {} not found", os_release_path.display());
     }
     
     let content = fs::read_to_string(os_release_path)
-        .with_context(|| format!("Failed to read {}", os_release_path))?;
+        .with_context(|| format!("Failed to read {}", os_release_path.display()))?;
     
     let mut id: Option<String> = None;
     let mut id_like: Option<String> = None;
@@ -61,14 +119,26 @@ fn detect_os() -> Result<OsType> {
     Ok(OsType::Debian)
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OsType {
     Debian,
     RedHat,
+    WindowsWSL2,
+}
+
+fn wsl2_instructions() -> &'static str {
+    "Windows (WSL2) was detected. Install podman inside your WSL distribution instead of on Windows:\n\
+     \x20 1. From PowerShell, make sure WSL is installed:  wsl --install\n\
+     \x20 2. Open your WSL distribution (e.g. Ubuntu) and run:\n\
+     \x20      sudo apt-get update && sudo apt-get install -y podman\n\
+     \x20 3. Re-run `overcode init` from inside the WSL distribution."
 }
 
 fn install_podman(os_type: OsType) -> Result<()> {
     let (cmd, args) = match os_type {
+        OsType::WindowsWSL2 => {
+            bail!("Automatic podman installation is not supported on WSL2.\n{}", wsl2_instructions());
+        }
         OsType::Debian => {
             ("apt-get", vec!["install", "-y", "podman"])
         }
@@ -103,23 +173,53 @@ fn install_podman(os_type: OsType) -> Result<()> {
     Ok(())
 }
 
-pub fn ensure_podman() -> Result<()> {
-    if check_podman_installed() {
+fn check_podman_status_with(
+    version: &dyn Fn() -> Option<String>,
+    detect: &dyn Fn() -> Result<OsType>,
+    install: &dyn Fn(OsType) -> Result<()>,
+) -> PodmanStatus {
+    if let Some(version) = version() {
         info!("podman is already installed");
-        return Ok(());
+        return PodmanStatus::AlreadyInstalled { version };
     }
     
     info!("podman is not installed. Detecting OS...");
-    let os_type = detect_os()?;
+    let os_type = match detect() {
+        Ok(os_type) => os_type,
+        Err(err) => return PodmanStatus::InstallFailed { reason: format!("{:#}", err) },
+    };
     info!("Detected OS type: {:?}", os_type);
     
-    install_podman(os_type)?;
+    if os_type == OsType::WindowsWSL2 {
+        return PodmanStatus::UnsupportedOs;
+    }
     
-    if !check_podman_installed() {
-        bail!("podman installation completed but verification failed");
+    if let Err(err) = install(os_type) {
+        return PodmanStatus::InstallFailed { reason: format!("{:#}", err) };
     }
     
-    info!("podman installation verified successfully");
-    Ok(())
+    match version() {
+        Some(version) => {
+            info!("podman installation verified successfully");
+            PodmanStatus::JustInstalled { version }
+        }
+        None => PodmanStatus::InstallFailed {
+            reason: "podman installation completed but verification failed".to_string(),
+        },
+    }
+}
+
+pub fn check_podman_status() -> PodmanStatus {
+    check_podman_status_with(&podman_version, &detect_os, &install_podman)
+}
+
+pub fn ensure_podman() -> Result<()> {
+    check_podman_status().into_result()
 }
 
+#[cfg(test)]
+#[path = "podman_install/driver/detect_os/detect_os.rs"]
+mod driver_detect_os_detect_os;
+#[cfg(test)]
+#[path = "podman_install/driver/status/status.rs"]
+mod driver_status_status;
@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::podman_install::{detect_os_from, is_wsl2, OsType};
+
+    #[test]
+    fn test_detect_os_wsl2_from_proc_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let proc_version = temp_dir.path().join("version");
+        let os_release = temp_dir.path().join("os-release");
+        fs::write(
+            &proc_version,
+            "Linux version 5.15.90.1-microsoft-standard-WSL2 (oe-user@oe-host) (gcc 11.2.0)",
+        ).unwrap();
+        fs::write(&os_release, "ID=ubuntu\n").unwrap();
+        
+        let os_type = detect_os_from(&proc_version, &os_release).unwrap();
+        
+        assert_eq!(os_type, OsType::WindowsWSL2);
+    }
+
+    #[test]
+    fn test_detect_os_native_linux_from_proc_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let proc_version = temp_dir.path().join("version");
+        let os_release = temp_dir.path().join("os-release");
+        fs::write(&proc_version, "Linux version 6.5.0-21-generic (buildd@ubuntu) (gcc 12.3.0)").unwrap();
+        fs::write(&os_release, "ID=fedora\n").unwrap();
+        
+        let os_type = detect_os_from(&proc_version, &os_release).unwrap();
+        
+        assert_eq!(os_type, OsType::RedHat);
+    }
+
+    #[test]
+    fn test_detect_os_without_proc_version_uses_os_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let os_release = temp_dir.path().join("os-release");
+        fs::write(&os_release, "ID=debian\n").unwrap();
+        
+        let os_type = detect_os_from(&temp_dir.path().join("missing"), &os_release).unwrap();
+        
+        assert_eq!(os_type, OsType::Debian);
+    }
+
+    #[test]
+    fn test_is_wsl2_variants() {
+        assert!(is_wsl2("Linux version 4.4.0-19041-Microsoft"));
+        assert!(is_wsl2("Linux version 5.15.0-WSL2"));
+        assert!(!is_wsl2("Linux version 6.5.0-21-generic"));
+    }
+}
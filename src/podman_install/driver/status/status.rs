@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use crate::podman_install::{check_podman_status_with, OsType, PodmanStatus};
+
+    fn installed() -> Option<String> {
+        Some("podman version 4.9.3".to_string())
+    }
+
+    fn not_installed() -> Option<String> {
+        None
+    }
+
+    fn debian() -> anyhow::Result<OsType> {
+        Ok(OsType::Debian)
+    }
+
+    fn install_ok(_: OsType) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    #[test]
+    fn test_status_already_installed() {
+        let status = check_podman_status_with(&installed, &debian, &|_| panic!("should not install"));
+
+        assert_eq!(status, PodmanStatus::AlreadyInstalled { version: "podman version 4.9.3".to_string() });
+        assert_eq!(
+            status.to_json().unwrap(),
+            r#"{"status":"already_installed","version":"podman version 4.9.3"}"#
+        );
+        assert!(status.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_status_just_installed() {
+        let installed_now = Cell::new(false);
+        let version = || installed_now.get().then(|| "podman version 5.0.0".to_string());
+        let install = |_: OsType| {
+            installed_now.set(true);
+            Ok(())
+        };
+
+        let status = check_podman_status_with(&version, &debian, &install);
+
+        assert_eq!(status, PodmanStatus::JustInstalled { version: "podman version 5.0.0".to_string() });
+        assert!(status.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_status_install_failed() {
+        let status = check_podman_status_with(&not_installed, &debian, &|_| anyhow::bail!("apt-get exited with 100"));
+
+        assert_eq!(status, PodmanStatus::InstallFailed { reason: "apt-get exited with 100".to_string() });
+        assert_eq!(
+            status.to_json().unwrap(),
+            r#"{"status":"install_failed","reason":"apt-get exited with 100"}"#
+        );
+        assert!(status.into_result().is_err());
+    }
+
+    #[test]
+    fn test_status_install_failed_when_verification_fails() {
+        let status = check_podman_status_with(&not_installed, &debian, &install_ok);
+
+        assert!(matches!(status, PodmanStatus::InstallFailed { .. }));
+    }
+
+    #[test]
+    fn test_status_unsupported_os() {
+        let status = check_podman_status_with(&not_installed, &|| Ok(OsType::WindowsWSL2), &|_| panic!("should not install"));
+
+        assert_eq!(status, PodmanStatus::UnsupportedOs);
+        assert_eq!(status.to_json().unwrap(), r#"{"status":"unsupported_os"}"#);
+        assert!(status.into_result().is_ok());
+    }
+}
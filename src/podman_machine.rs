@@ -0,0 +1,135 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use log::{info, warn};
+use crate::config::PodmanConfig;
+use crate::podman_command::{self, EngineUnavailable};
+
+const DEFAULT_START_TIMEOUT: Duration = Duration::from_secs(300);
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct MachineInfo {
+    pub name: String,
+    #[serde(default)]
+    pub default: bool,
+    #[serde(default)]
+    pub running: bool,
+}
+
+fn parse_machine_list(json: &str) -> Result<Vec<MachineInfo>> {
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(json).context("Failed to parse podman machine list output")
+}
+
+fn list_machines() -> Result<Vec<MachineInfo>> {
+    let output = podman_command::podman()
+        .args(["machine", "list", "--format", "json"])
+        .output()
+        .context("Failed to execute podman machine list")?;
+
+    if !output.status.success() {
+        bail!("podman machine list failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    parse_machine_list(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn start_machine(name: &str, timeout: Duration) -> Result<()> {
+    let mut child = podman_command::podman()
+        .args(["machine", "start", name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute podman machine start {}", name))?;
+
+    let started_at = Instant::now();
+    let mut reported_at = started_at;
+    loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for podman machine start")? {
+            if status.success() {
+                return Ok(());
+            }
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                std::io::Read::read_to_string(&mut pipe, &mut stderr).ok();
+            }
+            bail!("podman machine start {} exited with status {:?}: {}", name, status.code(), stderr.trim());
+        }
+        if started_at.elapsed() >= timeout {
+            child.kill().ok();
+            child.wait().ok();
+            bail!("podman machine start {} did not finish within {}s", name, timeout.as_secs());
+        }
+        if reported_at.elapsed() >= PROGRESS_INTERVAL {
+            info!("Still starting podman machine {} ({}s elapsed)...", name, started_at.elapsed().as_secs());
+            reported_at = Instant::now();
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn ensure_machine_with(
+    podman: Option<&PodmanConfig>,
+    list: &dyn Fn() -> Result<Vec<MachineInfo>>,
+    start: &dyn Fn(&str, Duration) -> Result<()>,
+) -> Result<()> {
+    let machines = match list() {
+        Ok(machines) => machines,
+        Err(err) => {
+            warn!("Could not list podman machines: {:#}", err);
+            return Ok(());
+        }
+    };
+    if machines.iter().any(|machine| machine.running) {
+        return Ok(());
+    }
+
+    let Some(machine) = machines
+        .iter()
+        .find(|machine| machine.default)
+        .or_else(|| machines.first())
+    else {
+        return Err(EngineUnavailable(
+            "No podman machine found; run `podman machine init` and `podman machine start`".to_string(),
+        ).into());
+    };
+    let name = machine.name.trim_end_matches('*');
+
+    let auto_start = podman.and_then(|p| p.auto_start_machine).unwrap_or(true);
+    if !auto_start {
+        return Err(EngineUnavailable(format!(
+            "podman machine {} is not running; run `podman machine start {}`",
+            name, name
+        )).into());
+    }
+
+    let timeout = podman
+        .and_then(|p| p.machine_start_timeout_secs)
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_START_TIMEOUT);
+    info!("Starting podman machine {} (this can take a minute)...", name);
+    start(name, timeout).map_err(|err| {
+        EngineUnavailable(format!(
+            "Failed to start podman machine {}: {:#}. Run `podman machine start {}` manually",
+            name, err, name
+        ))
+    })?;
+    info!("podman machine {} is running", name);
+    Ok(())
+}
+
+pub fn ensure_machine(podman: Option<&PodmanConfig>) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        return Ok(());
+    }
+    ensure_machine_with(podman, &list_machines, &start_machine)
+}
+
+#[cfg(test)]
+#[path = "podman_machine/driver/machine/machine.rs"]
+mod driver_machine_machine;
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use crate::config::PodmanConfig;
+    use crate::podman_machine::{ensure_machine_with, parse_machine_list, MachineInfo};
+
+    fn machine(name: &str, default: bool, running: bool) -> MachineInfo {
+        MachineInfo { name: name.to_string(), default, running }
+    }
+
+    #[test]
+    fn test_parse_machine_list_json() {
+        let json = r#"[
+  {"Name": "podman-machine-default*", "Default": true, "Running": false, "Starting": false, "VMType": "applehv"},
+  {"Name": "other", "Default": false, "Running": true}
+]"#;
+
+        let machines = parse_machine_list(json).unwrap();
+
+        assert_eq!(machines, vec![
+            machine("podman-machine-default*", true, false),
+            machine("other", false, true),
+        ]);
+        assert!(parse_machine_list("").unwrap().is_empty());
+        assert!(parse_machine_list("not json").is_err());
+    }
+
+    #[test]
+    fn test_running_machine_is_left_alone() {
+        let started = Mutex::new(Vec::new());
+
+        ensure_machine_with(
+            None,
+            &|| Ok(vec![machine("podman-machine-default", true, true)]),
+            &|name, _| {
+                started.lock().unwrap().push(name.to_string());
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert!(started.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_default_machine_is_started_with_timeout() {
+        let started = Mutex::new(Vec::new());
+        let config = PodmanConfig { machine_start_timeout_secs: Some(42), ..PodmanConfig::default() };
+
+        ensure_machine_with(
+            Some(&config),
+            &|| Ok(vec![machine("other", false, false), machine("podman-machine-default*", true, false)]),
+            &|name, timeout| {
+                started.lock().unwrap().push((name.to_string(), timeout));
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(*started.lock().unwrap(), vec![("podman-machine-default".to_string(), Duration::from_secs(42))]);
+    }
+
+    #[test]
+    fn test_auto_start_disabled_prints_instructions() {
+        let config = PodmanConfig { auto_start_machine: Some(false), ..PodmanConfig::default() };
+
+        let err = ensure_machine_with(
+            Some(&config),
+            &|| Ok(vec![machine("dev", true, false)]),
+            &|_, _| panic!("machine must not be started"),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("`podman machine start dev`"));
+    }
+
+    #[test]
+    fn test_missing_machine_suggests_init() {
+        let err = ensure_machine_with(None, &|| Ok(Vec::new()), &|_, _| Ok(())).unwrap_err();
+
+        assert!(err.to_string().contains("podman machine init"));
+    }
+
+    #[test]
+    fn test_start_failure_is_reported_with_instructions() {
+        let err = ensure_machine_with(
+            None,
+            &|| Ok(vec![machine("dev", true, false)]),
+            &|_, _| anyhow::bail!("did not finish within 300s"),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("did not finish within 300s"));
+        assert!(err.to_string().contains("`podman machine start dev`"));
+    }
+
+    #[test]
+    fn test_list_failure_is_not_fatal() {
+        assert!(ensure_machine_with(None, &|| anyhow::bail!("podman not found"), &|_, _| Ok(())).is_ok());
+    }
+}
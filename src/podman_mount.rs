@@ -1,11 +1,72 @@
-use std::path::Path;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use crate::config::{InvalidConfig, VolumeMount};
+
+pub fn canonical_root_dir(root_dir: &Path) -> Result<PathBuf> {
+    root_dir.canonicalize().with_context(|| {
+        format!(
+            "Failed to resolve root_dir {:?} to an absolute path. Was the directory removed?",
+            root_dir
+        )
+    })
+}
+
+pub fn absolute_root_dir(root_dir: &Path) -> Result<PathBuf> {
+    if root_dir.is_absolute() {
+        Ok(root_dir.to_path_buf())
+    } else {
+        canonical_root_dir(root_dir)
+    }
+}
+
+pub fn build_mount_args(root_dir: &Path) -> Result<Vec<String>> {
+    let root_dir = absolute_root_dir(root_dir)?;
+    if root_dir.parent().is_none() {
+        anyhow::bail!(
+            "Refusing to mount {:?} into the container: root_dir must not be the filesystem root. \
+             Place overcode.toml in a project directory or point --config at one.",
+            root_dir
+        );
+    }
 
-pub fn build_mount_args(root_dir: &Path) -> Vec<String> {
     let root_dir_str = root_dir.display().to_string();
     
-    vec![
+    Ok(vec![
         "-v".to_string(),
         format!("{}:{}", root_dir_str, root_dir_str),
-    ]
+    ])
+}
+
+pub fn resolve_env_file(env_file: Option<&str>, root_dir: &Path) -> Result<Option<PathBuf>> {
+    let Some(env_file) = env_file else {
+        return Ok(None);
+    };
+
+    let path = root_dir.join(env_file);
+    if !path.is_file() {
+        return Err(InvalidConfig(format!("env_file not found: {} (configured as '{}')", path.display(), env_file)).into());
+    }
+    Ok(Some(path))
+}
+
+pub fn build_env_file_args(env_file: Option<&str>, root_dir: &Path) -> Result<Vec<String>> {
+    Ok(resolve_env_file(env_file, root_dir)?
+        .map(|path| vec!["--env-file".to_string(), path.display().to_string()])
+        .unwrap_or_default())
+}
+
+pub fn build_volume_mount_args(volumes: &[VolumeMount]) -> Vec<String> {
+    volumes
+        .iter()
+        .flat_map(|volume| {
+            vec![
+                "-v".to_string(),
+                format!("{}:{}", volume.name, volume.container_path),
+            ]
+        })
+        .collect()
 }
 
+#[cfg(test)]
+#[path = "podman_mount/driver/env_file/env_file.rs"]
+mod driver_env_file_env_file;
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::InvalidConfig;
+    use crate::podman_mount::{build_env_file_args, resolve_env_file};
+
+    #[test]
+    fn test_env_file_resolves_against_root_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("secrets")).unwrap();
+        fs::write(temp_dir.path().join("secrets/test.env"), "TOKEN=abc\n").unwrap();
+
+        let path = resolve_env_file(Some("secrets/test.env"), temp_dir.path()).unwrap();
+
+        assert_eq!(path, Some(temp_dir.path().join("secrets/test.env")));
+    }
+
+    #[test]
+    fn test_env_file_args_point_at_resolved_path() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".env"), "TOKEN=abc\n").unwrap();
+
+        let args = build_env_file_args(Some(".env"), temp_dir.path()).unwrap();
+
+        assert_eq!(args, vec!["--env-file".to_string(), temp_dir.path().join(".env").display().to_string()]);
+    }
+
+    #[test]
+    fn test_unset_env_file_adds_no_args() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert!(build_env_file_args(None, temp_dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_env_file_is_config_error() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let err = build_env_file_args(Some("missing.env"), temp_dir.path()).unwrap_err();
+
+        assert!(err.downcast_ref::<InvalidConfig>().is_some());
+        assert!(err.to_string().contains("env_file not found"));
+        assert!(err.to_string().contains("(configured as 'missing.env')"));
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeSet;
+use std::path::Path;
+use log::info;
+use crate::config::{Config, VolumeMount};
+use crate::podman_command;
+
+const ROOT_LABEL: &str = "overcode.root";
+
+fn root_label(root_dir: &Path) -> String {
+    format!("{}={}", ROOT_LABEL, root_dir.display())
+}
+
+fn build_volume_create_args(name: &str, root_dir: &Path) -> Vec<String> {
+    vec![
+        "volume".to_string(),
+        "create".to_string(),
+        "--label".to_string(),
+        root_label(root_dir),
+        name.to_string(),
+    ]
+}
+
+pub fn volume_exists(name: &str) -> bool {
+    let output = podman_command::podman()
+        .args(["volume", "exists", name])
+        .output();
+    
+    match output {
+        Ok(result) => result.status.success(),
+        Err(_) => false,
+    }
+}
+
+pub fn create_named_volume(name: &str, root_dir: &Path) -> Result<()> {
+    info!("Creating volume: {}", name);
+    
+    let status = podman_command::podman()
+        .args(build_volume_create_args(name, root_dir))
+        .status()
+        .with_context(|| format!("Failed to execute podman volume create for volume: {}", name))?;
+    
+    if !status.success() {
+        bail!("Failed to create volume: {}. Command exited with status: {:?}", name, status.code());
+    }
+    
+    Ok(())
+}
+
+pub fn destroy_named_volume(name: &str) -> Result<()> {
+    info!("Removing volume: {}", name);
+    
+    let status = podman_command::podman()
+        .args(["volume", "rm", name])
+        .status()
+        .with_context(|| format!("Failed to execute podman volume rm for volume: {}", name))?;
+    
+    if !status.success() {
+        bail!("Failed to remove volume: {}. Command exited with status: {:?}", name, status.code());
+    }
+    
+    Ok(())
+}
+
+fn declared_volumes(config: &Config) -> Vec<&VolumeMount> {
+    let Some(command) = &config.command else {
+        return Vec::new();
+    };
+    
+    command.test
+        .iter()
+        .chain(command.run.iter())
+        .chain(command.custom.values())
+        .flat_map(|run_config| run_config.volumes.iter())
+        .collect()
+}
+
+fn unused_volumes(managed: &[String], config: &Config) -> Vec<String> {
+    let declared: BTreeSet<&str> = declared_volumes(config)
+        .into_iter()
+        .map(|volume| volume.name.as_str())
+        .collect();
+    
+    managed
+        .iter()
+        .filter(|name| !declared.contains(name.as_str()))
+        .cloned()
+        .collect()
+}
+
+fn list_managed_volumes(root_dir: &Path) -> Result<Vec<String>> {
+    let output = podman_command::podman()
+        .args([
+            "volume",
+            "ls",
+            "--filter",
+            &format!("label={}", root_label(root_dir)),
+            "--format",
+            "{{.Name}}",
+        ])
+        .output()
+        .context("Failed to execute podman volume ls")?;
+    
+    if !output.status.success() {
+        bail!("Failed to list volumes. Command exited with status: {:?}", output.status.code());
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+pub fn ensure_volumes(config: &Config, root_dir: &Path) -> Result<()> {
+    let names: BTreeSet<&str> = declared_volumes(config)
+        .into_iter()
+        .map(|volume| volume.name.as_str())
+        .collect();
+    
+    for name in names {
+        if volume_exists(name) {
+            info!("Volume already exists: {}", name);
+        } else {
+            create_named_volume(name, root_dir)?;
+        }
+    }
+    
+    Ok(())
+}
+
+pub fn process_gc_volumes(config: &Config, root_dir: &Path, dry_run: bool) -> Result<()> {
+    let managed = list_managed_volumes(root_dir)?;
+    let unused = unused_volumes(&managed, config);
+    
+    if unused.is_empty() {
+        info!("No unused volumes found");
+        return Ok(());
+    }
+    
+    if dry_run {
+        for name in &unused {
+            info!("Would remove volume: {}", name);
+        }
+        return Ok(());
+    }
+    
+    for name in &unused {
+        destroy_named_volume(name)?;
+    }
+    
+    info!("Removed {} unused volume(s)", unused.len());
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "podman_volume/driver/config/config.rs"]
+mod driver_config_config;
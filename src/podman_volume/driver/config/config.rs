@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::podman_volume::{build_volume_create_args, declared_volumes, unused_volumes};
+
+    fn load(toml_content: &str) -> Config {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        Config::load(&config_path).unwrap()
+    }
+
+    const CONFIG: &str = r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+volumes = [
+    { name = "pgdata", container_path = "/var/lib/postgresql/data" },
+]
+
+[command.run]
+command = "cargo"
+args = ["run"]
+volumes = [
+    { name = "cache", container_path = "/cache" },
+]
+
+[command.custom.seed]
+command = "psql"
+args = []
+volumes = [
+    { name = "pgdata", container_path = "/data" },
+]
+"#;
+
+    #[test]
+    fn test_volumes_are_parsed_from_every_command() {
+        let config = load(CONFIG);
+        
+        let mut names: Vec<&str> = declared_volumes(&config)
+            .into_iter()
+            .map(|volume| volume.name.as_str())
+            .collect();
+        names.sort();
+        
+        assert_eq!(names, vec!["cache", "pgdata", "pgdata"]);
+        let test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        assert_eq!(test.volumes[0].container_path, "/var/lib/postgresql/data");
+    }
+
+    #[test]
+    fn test_volumes_default_to_empty() {
+        let config = load(r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+"#);
+        
+        assert!(declared_volumes(&config).is_empty());
+    }
+
+    #[test]
+    fn test_unused_volumes_excludes_declared_names() {
+        let config = load(CONFIG);
+        let managed = vec![
+            "pgdata".to_string(),
+            "old-fixtures".to_string(),
+            "cache".to_string(),
+        ];
+        
+        assert_eq!(unused_volumes(&managed, &config), vec!["old-fixtures"]);
+    }
+
+    #[test]
+    fn test_build_volume_create_args_labels_project_root() {
+        let args = build_volume_create_args("pgdata", Path::new("/work/project"));
+        
+        assert_eq!(
+            args,
+            vec!["volume", "create", "--label", "overcode.root=/work/project", "pgdata"]
+        );
+    }
+}
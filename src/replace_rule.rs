@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use log::info;
+use regex::{Captures, Regex};
+use crate::config::ReplaceRule;
+
+fn expand_groups(template: &str, caps: &Captures) -> String {
+    let mut expanded = String::new();
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+
+        if chars.next_if_eq(&'{').is_some() {
+            let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+            let capture = match name.parse::<usize>() {
+                Ok(index) => caps.get(index),
+                Err(_) => caps.name(&name),
+            };
+            expanded.push_str(capture.map_or("", |m| m.as_str()));
+            continue;
+        }
+
+        let mut digits = String::new();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            digits.push(digit);
+        }
+        match digits.parse::<usize>() {
+            Ok(index) => expanded.push_str(caps.get(index).map_or("", |m| m.as_str())),
+            Err(_) => expanded.push('$'),
+        }
+    }
+
+    expanded
+}
+
+pub fn apply_replace_rules<'a>(
+    input: &str,
+    rules: impl IntoIterator<Item = &'a ReplaceRule>,
+) -> Result<String> {
+    let mut processed = input.to_string();
+
+    for rule in rules {
+        info!("Applying replace_rule: pattern = '{}', replace = '{}'", rule.pattern, rule.replace);
+
+        let pattern = if rule.case_insensitive {
+            format!("(?i){}", rule.pattern)
+        } else {
+            rule.pattern.clone()
+        };
+        let re = Regex::new(&pattern)
+            .with_context(|| format!("Invalid replace_rule pattern: {}", rule.pattern))?;
+        let expand = |caps: &Captures| expand_groups(&rule.replace, caps);
+        let replaced = if rule.global {
+            re.replace_all(&processed, expand)
+        } else {
+            re.replace(&processed, expand)
+        };
+
+        processed = replaced.into_owned();
+        info!("After replace_rule application: '{}' -> '{}'", input, processed);
+    }
+
+    Ok(processed)
+}
+
+#[cfg(test)]
+#[path = "replace_rule/driver/expand/expand.rs"]
+mod driver_expand_expand;
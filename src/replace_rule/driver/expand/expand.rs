@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::{ReplaceRule, ReplaceTarget};
+    use crate::replace_rule::apply_replace_rules;
+
+    fn rule(pattern: &str, replace: &str) -> ReplaceRule {
+        ReplaceRule {
+            pattern: pattern.to_string(),
+            replace: replace.to_string(),
+            replace_target: ReplaceTarget::DriverFile,
+            global: false,
+            case_insensitive: false,
+        }
+    }
+
+    #[test]
+    fn test_apply_replace_rules_with_fewer_than_three_groups() {
+        let rules = [rule("src/(.+)\\.rs", "$1")];
+
+        assert_eq!(apply_replace_rules("src/cli.rs", &rules).unwrap(), "cli");
+    }
+
+    #[test]
+    fn test_apply_replace_rules_with_more_than_nine_groups() {
+        let rules = [rule("(a)(b)(c)(d)(e)(f)(g)(h)(i)(j)(k)", "$11$10_$1")];
+
+        assert_eq!(apply_replace_rules("abcdefghijk", &rules).unwrap(), "kj_a");
+    }
+
+    #[test]
+    fn test_apply_replace_rules_with_named_groups() {
+        let rules = [rule("src/(?P<mod>[^/]+)/(?P<file>.+)\\.rs", "${mod}/${file}_${1}.rs")];
+
+        assert_eq!(apply_replace_rules("src/cli/args.rs", &rules).unwrap(), "cli/args_cli.rs");
+    }
+
+    #[test]
+    fn test_apply_replace_rules_keeps_literal_dollar() {
+        let rules = [rule("price", "$USD $0")];
+
+        assert_eq!(apply_replace_rules("price", &rules).unwrap(), "$USD price");
+    }
+
+    #[test]
+    fn test_apply_replace_rules_applies_rules_in_order() {
+        let rules = [rule("a", "b"), rule("b", "c")];
+
+        assert_eq!(apply_replace_rules("a", &rules).unwrap(), "c");
+    }
+
+    #[test]
+    fn test_apply_replace_rules_invalid_pattern() {
+        let rules = [rule("(", "$1")];
+
+        let error_msg = apply_replace_rules("anything", &rules).unwrap_err().to_string();
+        assert!(error_msg.contains("Invalid replace_rule pattern"));
+    }
+}
@@ -1,99 +1,407 @@
 use anyhow::Context;
+use std::fmt;
+use std::fs;
 use std::path::Path;
-use std::process::Command;
-use std::io::Write;
-use crate::config::Config;
-use log::info;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use crate::config::{CommandConfig, Config, InvalidConfig};
+use crate::env_file;
+use crate::podman_command;
+use crate::podman_mount;
+use crate::replace_rule;
+use crate::run_history::{self, RunRecord};
+use log::{info, warn};
 
-fn execute_run_command(
+const RUN_ARGS_ENV: &str = "OVERCODE_RUN_ARGS";
+const ROOT_DIR_PLACEHOLDER: &str = "{root_dir}";
+const ELAPSED_PLACEHOLDER: &str = "{elapsed}";
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+#[derive(Debug)]
+pub struct RunTimedOut {
+    pub timeout: Duration,
+}
+
+impl fmt::Display for RunTimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Run command timed out after {}s and was killed", self.timeout.as_secs())
+    }
+}
+
+impl std::error::Error for RunTimedOut {}
+
+#[derive(Debug)]
+pub struct RunFailed {
+    pub code: Option<i32>,
+}
+
+impl fmt::Display for RunFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Run command failed with exit code: {:?}", self.code)
+    }
+}
+
+impl std::error::Error for RunFailed {}
+
+fn strip_shell_quotes(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+fn read_run_args_file(path: &Path) -> anyhow::Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read run_args_file: {}", path.display()))?;
+
+    Ok(crate::config::strip_bom(&content)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| strip_shell_quotes(line).to_string())
+        .collect())
+}
+
+fn collect_extra_args(
     run_config: &crate::config::RunTestConfig,
     root_dir: &Path,
-    extra_args: &[String],
-) -> anyhow::Result<()> {
+    env_args: Option<&str>,
+    cli_args: &[String],
+) -> anyhow::Result<Vec<String>> {
+    let mut extra_args = Vec::new();
+
+    if let Some(ref run_args_file) = run_config.run_args_file {
+        let file_args = read_run_args_file(&root_dir.join(run_args_file))?;
+        info!("Arguments from run_args_file: {:?}", file_args);
+        extra_args.extend(file_args);
+    }
+
+    if let Some(env_args) = env_args {
+        let env_args: Vec<String> = env_args.split_whitespace().map(String::from).collect();
+        info!("Arguments from {}: {:?}", RUN_ARGS_ENV, env_args);
+        extra_args.extend(env_args);
+    }
+
+    extra_args.extend_from_slice(cli_args);
+
+    Ok(extra_args)
+}
+
+fn build_run_podman_args(
+    run_config: &crate::config::RunTestConfig,
+    image: &str,
+    root_dir: &Path,
+    run_id: &str,
+    container_name: Option<&str>,
+    processed_args: Vec<String>,
+) -> anyhow::Result<Vec<String>> {
+    let root_dir_str = podman_mount::absolute_root_dir(root_dir)?.display().to_string();
+    let mut podman_args = vec![
+        "run".to_string(),
+        "--rm".to_string(),
+    ];
+    if let Some(container_name) = container_name {
+        podman_args.push("--name".to_string());
+        podman_args.push(container_name.to_string());
+    }
+    podman_args.extend(podman_mount::build_mount_args(root_dir)?);
+    podman_args.extend(podman_mount::build_volume_mount_args(&run_config.volumes));
+    podman_args.extend(podman_mount::build_env_file_args(run_config.env_file.as_deref(), Path::new(&root_dir_str))?);
+    if run_config.inject_env {
+        podman_args.extend(env_file::build_env_args(&env_file::context_env(&root_dir_str, run_id)));
+    }
+    podman_args.extend([
+        "-w".to_string(),
+        root_dir_str,
+        image.to_string(),
+        run_config.command.clone(),
+    ]);
+    podman_args.extend(processed_args);
+    Ok(podman_args)
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    format!("{:.2}", elapsed.as_secs_f64())
+}
+
+fn substitute_run_args(
+    run_config: &crate::config::RunTestConfig,
+    root_dir: &Path,
+    elapsed: Option<Duration>,
+) -> anyhow::Result<Vec<String>> {
     let root_dir_str = root_dir.display().to_string();
+    let root_dir_index = run_config.args.iter().position(|arg| arg.contains(ROOT_DIR_PLACEHOLDER));
     
-    let mut processed_args: Vec<String> = run_config.args
+    run_config.args
         .iter()
-        .map(|arg| {
-            arg.replace("{root_dir}", &root_dir_str)
+        .enumerate()
+        .map(|(index, arg)| {
+            if arg.contains(ELAPSED_PLACEHOLDER) && root_dir_index.is_none_or(|root_dir_index| index <= root_dir_index) {
+                return Err(InvalidConfig(format!("{} is only valid in args after {}: {}", ELAPSED_PLACEHOLDER, ROOT_DIR_PLACEHOLDER, arg)).into());
+            }
+            let arg = replace_rule::apply_replace_rules(arg, &run_config.replace_rule)?;
+            let arg = arg.replace(ROOT_DIR_PLACEHOLDER, &root_dir_str);
+            Ok(match elapsed {
+                Some(elapsed) => arg.replace(ELAPSED_PLACEHOLDER, &format_elapsed(elapsed)),
+                None => arg,
+            })
         })
-        .collect();
-    
-    processed_args.extend_from_slice(extra_args);
-    
+        .collect()
+}
+
+fn previous_run_elapsed(root_dir: &Path) -> Option<Duration> {
+    match run_history::read_last(&run_history::history_path(root_dir), 1) {
+        Ok(records) => records
+            .last()
+            .and_then(|record| Duration::try_from_secs_f64(record.duration_secs).ok()),
+        Err(err) => {
+            warn!("Failed to read run history for {}: {:#}", ELAPSED_PLACEHOLDER, err);
+            None
+        }
+    }
+}
+
+fn rewrite_extra_args(
+    run_config: &crate::config::RunTestConfig,
+    extra_args: &[String],
+) -> anyhow::Result<Vec<String>> {
+    extra_args
+        .iter()
+        .map(|arg| replace_rule::apply_replace_rules(arg, &run_config.replace_rule))
+        .collect()
+}
+
+#[derive(Debug)]
+enum RunOutcome {
+    Exited(ExitStatus),
+    TimedOut,
+}
+
+fn spawn_output_forwarder(
+    mut pipe: impl Read + Send + 'static,
+    mut sink: impl Write + Send + 'static,
+    last_output: Arc<Mutex<Instant>>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut buffer = [0u8; 8192];
+        while let Ok(read) = pipe.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            let _ = sink.write_all(&buffer[..read]);
+            let _ = sink.flush();
+            *last_output.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Instant::now();
+        }
+    })
+}
+
+fn format_heartbeat(elapsed: Duration) -> String {
+    format!("still running, {}m elapsed", elapsed.as_secs() / 60)
+}
+
+fn wait_for_run(
+    mut child: Child,
+    timeout: Option<Duration>,
+    heartbeat_interval: Duration,
+    on_timeout: impl FnOnce(),
+) -> anyhow::Result<RunOutcome> {
+    let started = Instant::now();
+    let last_output = Arc::new(Mutex::new(started));
+    let forwarders: Vec<JoinHandle<()>> = [
+        child.stdout.take().map(|pipe| spawn_output_forwarder(pipe, std::io::stdout(), Arc::clone(&last_output))),
+        child.stderr.take().map(|pipe| spawn_output_forwarder(pipe, std::io::stderr(), Arc::clone(&last_output))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut last_heartbeat = started;
+    let outcome = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for run command")? {
+            break RunOutcome::Exited(status);
+        }
+        if timeout.is_some_and(|timeout| started.elapsed() >= timeout) {
+            on_timeout();
+            let _ = child.kill();
+            child.wait().context("Failed to wait for killed run command")?;
+            break RunOutcome::TimedOut;
+        }
+
+        let last_activity = (*last_output.lock().unwrap_or_else(|poisoned| poisoned.into_inner())).max(last_heartbeat);
+        if last_activity.elapsed() >= heartbeat_interval {
+            let _ = writeln!(std::io::stderr(), "{}", format_heartbeat(started.elapsed()));
+            last_heartbeat = Instant::now();
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    for forwarder in forwarders {
+        let _ = forwarder.join();
+    }
+
+    Ok(outcome)
+}
+
+fn execute_run_command(
+    run_config: &crate::config::RunTestConfig,
+    root_dir: &Path,
+    processed_args: Vec<String>,
+    timeout: Option<Duration>,
+) -> anyhow::Result<RunOutcome> {
     if let Some(ref image) = run_config.image {
         info!("Executing in podman container (image: {}): {} {:?}", image, run_config.command, processed_args);
         
-        let mut podman_args = vec![
-            "run".to_string(),
-            "--rm".to_string(),
-            "-v".to_string(),
-            format!("{}:{}", root_dir_str, root_dir_str),
-            "-w".to_string(),
-            root_dir_str.clone(),
-            image.clone(),
-            run_config.command.clone(),
-        ];
-        podman_args.extend(processed_args);
+        let run_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System clock is before the UNIX epoch")?
+            .as_secs()
+            .to_string();
+        let container_name = format!("overcode-run-{}", std::process::id());
+        let podman_args = build_run_podman_args(
+            run_config,
+            image,
+            root_dir,
+            &run_id,
+            timeout.map(|_| container_name.as_str()),
+            processed_args,
+        )?;
         
-        let output = Command::new("podman")
+        let child = podman_command::podman()
             .args(&podman_args)
-            .output()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .with_context(|| format!("Failed to execute podman run for image: {}", image))?;
         
-        std::io::stdout().write_all(&output.stdout)
-            .context("Failed to write stdout")?;
-        std::io::stderr().write_all(&output.stderr)
-            .context("Failed to write stderr")?;
-        
-        if !output.status.success() {
-            anyhow::bail!(
-                "Run command failed with exit code: {:?}",
-                output.status.code()
-            );
-        }
+        wait_for_run(child, timeout, HEARTBEAT_INTERVAL, || {
+            let _ = podman_command::podman()
+                .args(["rm", "-f", &container_name])
+                .output();
+        })
     } else {
         info!("Executing: {} {:?} (from {:?})", run_config.command, processed_args, root_dir);
         
-        let output = Command::new(&run_config.command)
+        let child = Command::new(&run_config.command)
             .args(&processed_args)
             .current_dir(root_dir)
-            .output()
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
             .with_context(|| format!("Failed to execute command: {}", run_config.command))?;
         
-        std::io::stdout().write_all(&output.stdout)
-            .context("Failed to write stdout")?;
-        std::io::stderr().write_all(&output.stderr)
-            .context("Failed to write stderr")?;
-        
-        if !output.status.success() {
-            anyhow::bail!(
-                "Run command failed with exit code: {:?}",
-                output.status.code()
-            );
-        }
+        wait_for_run(child, timeout, HEARTBEAT_INTERVAL, || {})
     }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub name: Option<String>,
+    pub timeout: Option<Duration>,
+}
+
+fn select_run_config<'a>(
+    command: Option<&'a CommandConfig>,
+    name: Option<&str>,
+) -> anyhow::Result<&'a crate::config::RunTestConfig> {
+    match name {
+        Some(name) => command
+            .and_then(|c| c.custom.get(name))
+            .ok_or_else(|| InvalidConfig(format!("[command.custom.{}] section not found in overcode.toml", name)).into()),
+        None => command
+            .and_then(|c| c.run.as_ref())
+            .ok_or_else(|| InvalidConfig("[command.run] section not found in overcode.toml".to_string()).into()),
+    }
+}
+
+pub fn process_run_history(root_dir: &Path, count: usize) -> anyhow::Result<()> {
+    let records = run_history::read_last(&run_history::history_path(root_dir), count)?;
+    if records.is_empty() {
+        info!("No run history recorded yet");
+        return Ok(());
+    }
+    
+    std::io::stdout().write_all(run_history::format_history(&records).as_bytes())
+        .context("Failed to write run history")?;
     
     Ok(())
 }
 
-pub fn process_run(config_path: &Path, extra_args: &[String]) -> anyhow::Result<()> {
-    let config = Config::load(config_path)?;
-    let root_dir = config_path
-        .parent()
-            .ok_or_else(|| anyhow::anyhow!("Config file has no parent directory"))?;
+pub fn process_run(
+    config: &Config,
+    root_dir: &Path,
+    extra_args: &[String],
+    options: &RunOptions,
+) -> anyhow::Result<()> {
     
-    let run_config = config.command
-        .as_ref()
-        .and_then(|c| c.run.as_ref())
-        .ok_or_else(|| anyhow::anyhow!("[command.run] section not found in overcode.toml"))?;
+    let run_config = select_run_config(config.command.as_ref(), options.name.as_deref())?;
+    let root_dir = &podman_mount::canonical_root_dir(root_dir)?;
+    if run_config.image.is_some() {
+        podman_command::ensure_engine(config.podman.as_ref())?;
+    }
+    
+    let env_args = std::env::var(RUN_ARGS_ENV).ok();
+    let extra_args = collect_extra_args(run_config, root_dir, env_args.as_deref(), extra_args)?;
+    let extra_args = rewrite_extra_args(run_config, &extra_args)?;
     
-    info!("Executing run command");
+    match options.name {
+        Some(ref name) => info!("Executing custom run command: {}", name),
+        None => info!("Executing run command"),
+    }
     if !extra_args.is_empty() {
         info!("Additional arguments: {:?}", extra_args);
     }
     
-    execute_run_command(run_config, root_dir, extra_args)?;
+    let uses_elapsed = run_config.args.iter().any(|arg| arg.contains(ELAPSED_PLACEHOLDER));
+    let previous_elapsed = if uses_elapsed { previous_run_elapsed(root_dir) } else { None };
+    let mut processed_args = substitute_run_args(run_config, root_dir, previous_elapsed)?;
+    processed_args.extend_from_slice(&extra_args);
+    
+    let timeout = options.timeout.or(run_config.timeout_secs.map(Duration::from_secs));
+    let started = Instant::now();
+    let outcome = execute_run_command(run_config, root_dir, processed_args, timeout)?;
+    let elapsed = started.elapsed();
+    info!("Run completed in {:.2}s", elapsed.as_secs_f64());
+    let args = substitute_run_args(run_config, root_dir, Some(elapsed))?;
+    let exit_code = match outcome {
+        RunOutcome::Exited(status) => status.code(),
+        RunOutcome::TimedOut => None,
+    };
+    
+    let record = RunRecord {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("System clock is before the UNIX epoch")?
+            .as_secs(),
+        command: run_config.command.clone(),
+        args,
+        image: run_config.image.clone(),
+        extra_args,
+        exit_code,
+        duration_secs: elapsed.as_secs_f64(),
+    };
+    let max_entries = config.run_history
+        .as_ref()
+        .and_then(|history| history.max_entries)
+        .unwrap_or(run_history::DEFAULT_MAX_ENTRIES);
+    if let Err(err) = run_history::append_record(&run_history::history_path(root_dir), record, max_entries) {
+        warn!("Failed to record run history: {:#}", err);
+    }
+    
+    match outcome {
+        RunOutcome::Exited(status) if !status.success() => {
+            return Err(RunFailed { code: status.code() }.into());
+        }
+        RunOutcome::Exited(_) => {}
+        RunOutcome::TimedOut => {
+            return Err(RunTimedOut { timeout: timeout.unwrap_or_default() }.into());
+        }
+    }
     
     info!("Run command completed successfully");
     
@@ -1,8 +1,25 @@
 #[cfg(test)]
 mod tests {
     use std::fs;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
     use tempfile::TempDir;
     use crate::config::Config;
+    use crate::run::{
+        build_run_podman_args, collect_extra_args, format_heartbeat, rewrite_extra_args, select_run_config,
+        substitute_run_args, wait_for_run, RunOutcome,
+    };
+
+    fn load_run_config(temp_dir: &TempDir, toml_content: &str) -> crate::config::RunTestConfig {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        Config::load(&config_path)
+            .unwrap()
+            .command
+            .and_then(|c| c.run)
+            .expect("run config should exist")
+    }
 
 
     #[test]
@@ -334,5 +351,393 @@ args = ["test"]
         
         assert!(run_config.is_none());
     }
-}
 
+    #[test]
+    fn test_collect_extra_args_from_run_args_file() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("run_args.txt"),
+            "--port\n\"8080\"\n\n'hello world'\n",
+        ).unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+run_args_file = "run_args.txt"
+"#);
+
+        let extra_args = collect_extra_args(&run_config, temp_dir.path(), None, &[]).unwrap();
+
+        assert_eq!(extra_args, vec!["--port", "8080", "hello world"]);
+    }
+
+    #[test]
+    fn test_collect_extra_args_run_args_file_with_bom_and_crlf() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("run_args.txt"), "\u{feff}--port\r\n\"8080\"\r\n").unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+run_args_file = "run_args.txt"
+"#);
+
+        let extra_args = collect_extra_args(&run_config, temp_dir.path(), None, &[]).unwrap();
+
+        assert_eq!(extra_args, vec!["--port", "8080"]);
+    }
+
+    #[test]
+    fn test_collect_extra_args_missing_run_args_file_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+run_args_file = "missing.txt"
+"#);
+
+        let result = collect_extra_args(&run_config, temp_dir.path(), None, &[]);
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("run_args_file"));
+    }
+
+    #[test]
+    fn test_collect_extra_args_from_env() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+"#);
+
+        let extra_args = collect_extra_args(&run_config, temp_dir.path(), Some("  --verbose  --jobs 4 "), &[]).unwrap();
+
+        assert_eq!(extra_args, vec!["--verbose", "--jobs", "4"]);
+    }
+
+    #[test]
+    fn test_collect_extra_args_from_cli() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+"#);
+        let cli_args = vec!["world".to_string()];
+
+        let extra_args = collect_extra_args(&run_config, temp_dir.path(), None, &cli_args).unwrap();
+
+        assert_eq!(extra_args, vec!["world"]);
+    }
+
+    #[test]
+    fn test_collect_extra_args_merge_order() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("run_args.txt"), "from-file\n").unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "echo"
+args = []
+run_args_file = "run_args.txt"
+"#);
+        let cli_args = vec!["from-cli".to_string()];
+
+        let extra_args = collect_extra_args(&run_config, temp_dir.path(), Some("from-env"), &cli_args).unwrap();
+
+        assert_eq!(extra_args, vec!["from-file", "from-env", "from-cli"]);
+    }
+
+    #[test]
+    fn test_select_run_config_custom_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.run]
+command = "cargo"
+args = ["run"]
+
+[command.custom.serve]
+command = "cargo"
+args = ["run", "--bin", "server"]
+
+[command.custom.migrate]
+image = "docker.io/library/postgres:16"
+command = "psql"
+args = ["-f", "migrate.sql"]
+"#).unwrap();
+        
+        let config = Config::load(&config_path).unwrap();
+        let command = config.command.as_ref();
+        assert_eq!(command.unwrap().custom.len(), 2);
+        
+        let serve = select_run_config(command, Some("serve")).unwrap();
+        assert_eq!(serve.args, vec!["run", "--bin", "server"]);
+        
+        let migrate = select_run_config(command, Some("migrate")).unwrap();
+        assert_eq!(migrate.command, "psql");
+        assert_eq!(migrate.image.as_deref(), Some("docker.io/library/postgres:16"));
+        
+        let default = select_run_config(command, None).unwrap();
+        assert_eq!(default.args, vec!["run"]);
+    }
+
+    #[test]
+    fn test_select_run_config_unknown_custom_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.run]
+command = "cargo"
+args = ["run"]
+"#).unwrap();
+        
+        let config = Config::load(&config_path).unwrap();
+        let result = select_run_config(config.command.as_ref(), Some("seed"));
+        
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("[command.custom.seed]"));
+    }
+
+    #[test]
+    fn test_run_podman_args_include_context_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["run"]
+"#);
+
+        let args = build_run_podman_args(
+            &run_config,
+            "docker.io/library/rust:latest",
+            Path::new("/repo"),
+            "1700000000",
+            None,
+            vec!["run".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(
+            args,
+            vec![
+                "run", "--rm",
+                "-v", "/repo:/repo",
+                "-e", "OVERCODE_ROOT_DIR=/repo",
+                "-e", "OVERCODE_RUN_ID=1700000000",
+                "-w", "/repo",
+                "docker.io/library/rust:latest",
+                "cargo", "run",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_podman_args_without_context_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["run"]
+inject_env = false
+"#);
+
+        let args = build_run_podman_args(
+            &run_config,
+            "docker.io/library/rust:latest",
+            Path::new("/repo"),
+            "1700000000",
+            None,
+            vec![],
+        )
+        .unwrap();
+
+        assert!(!args.contains(&"-e".to_string()));
+    }
+
+    #[test]
+    fn test_run_podman_args_combine_env_file_and_context_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(".env"), "TOKEN=abc\n").unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["run"]
+env_file = ".env"
+"#);
+        let root_dir = temp_dir.path().display().to_string();
+        let env_file = temp_dir.path().join(".env").display().to_string();
+
+        let args = build_run_podman_args(
+            &run_config,
+            "docker.io/library/rust:latest",
+            temp_dir.path(),
+            "1700000000",
+            None,
+            vec![],
+        )
+        .unwrap();
+
+        let env_file_index = args.iter().position(|arg| arg == "--env-file").unwrap();
+        assert_eq!(args[env_file_index + 1], env_file);
+        let root_env = format!("OVERCODE_ROOT_DIR={}", root_dir);
+        let root_env_index = args.iter().position(|arg| *arg == root_env).unwrap();
+        assert_eq!(args[root_env_index - 1], "-e");
+        assert!(env_file_index < root_env_index);
+    }
+
+    #[test]
+    fn test_run_podman_args_with_relative_root_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["run"]
+"#);
+        let root_dir = Path::new("src").canonicalize().unwrap().display().to_string();
+
+        let args = build_run_podman_args(
+            &run_config,
+            "docker.io/library/rust:latest",
+            Path::new("src"),
+            "1700000000",
+            None,
+            vec![],
+        )
+        .unwrap();
+
+        assert!(args.contains(&format!("{}:{}", root_dir, root_dir)));
+        assert!(args.contains(&format!("OVERCODE_ROOT_DIR={}", root_dir)));
+        let workdir = args.iter().position(|arg| arg == "-w").unwrap();
+        assert_eq!(args[workdir + 1], root_dir);
+    }
+
+    #[test]
+    fn test_run_replace_rule_rewrites_args_before_substitution() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "cargo"
+args = ["run", "--bin", "host/tools/serve", "{root_dir}/host/data"]
+replace_rule = [{ pattern = "host/", replace = "container/", global = true }]
+"#);
+
+        let args = substitute_run_args(&run_config, Path::new("/repo"), None).unwrap();
+
+        assert_eq!(args, vec!["run", "--bin", "container/tools/serve", "/repo/container/data"]);
+    }
+
+    #[test]
+    fn test_run_elapsed_placeholder_resolved_in_second_pass() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "sh"
+args = ["-c", "cd {root_dir}", "echo done in {elapsed}s"]
+"#);
+
+        let first_pass = substitute_run_args(&run_config, Path::new("/repo"), None).unwrap();
+        let second_pass = substitute_run_args(&run_config, Path::new("/repo"), Some(Duration::from_millis(1234))).unwrap();
+
+        assert_eq!(first_pass, vec!["-c", "cd /repo", "echo done in {elapsed}s"]);
+        assert_eq!(second_pass, vec!["-c", "cd /repo", "echo done in 1.23s"]);
+    }
+
+    #[test]
+    fn test_run_elapsed_placeholder_before_root_dir_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "sh"
+args = ["-c", "echo done in {elapsed}s", "{root_dir}"]
+"#);
+
+        let error_msg = substitute_run_args(&run_config, Path::new("/repo"), None).unwrap_err().to_string();
+
+        assert!(error_msg.contains("{elapsed} is only valid in args after {root_dir}"));
+    }
+
+    #[test]
+    fn test_run_replace_rule_rewrites_extra_args() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+command = "cargo"
+args = ["run"]
+replace_rule = [{ pattern = "^/home/([^/]+)/(.+)$", replace = "/workspace/$2" }]
+"#);
+
+        let extra_args = rewrite_extra_args(
+            &run_config,
+            &["--input".to_string(), "/home/user/data/in.csv".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(extra_args, vec!["--input", "/workspace/data/in.csv"]);
+    }
+
+    #[test]
+    fn test_run_podman_args_name_container_for_timeout() {
+        let temp_dir = TempDir::new().unwrap();
+        let run_config = load_run_config(&temp_dir, r#"
+[command.run]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["run"]
+timeout_secs = 30
+"#);
+
+        let args = build_run_podman_args(
+            &run_config,
+            "docker.io/library/rust:latest",
+            Path::new("/repo"),
+            "1700000000",
+            Some("overcode-run-42"),
+            vec![],
+        )
+        .unwrap();
+
+        assert_eq!(run_config.timeout_secs, Some(30));
+        assert_eq!(&args[..4], &["run", "--rm", "--name", "overcode-run-42"]);
+    }
+
+    #[test]
+    fn test_wait_for_run_kills_command_after_timeout() {
+        let child = Command::new("sleep")
+            .arg("10")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut timed_out = false;
+
+        let outcome = wait_for_run(child, Some(Duration::from_millis(100)), Duration::from_secs(60), || timed_out = true).unwrap();
+
+        assert!(matches!(outcome, RunOutcome::TimedOut));
+        assert!(timed_out);
+    }
+
+    #[test]
+    fn test_wait_for_run_returns_exit_status() {
+        let child = Command::new("sh")
+            .args(["-c", "exit 3"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let outcome = wait_for_run(child, Some(Duration::from_secs(10)), Duration::from_secs(60), || {}).unwrap();
+
+        assert!(matches!(outcome, RunOutcome::Exited(status) if status.code() == Some(3)));
+    }
+
+    #[test]
+    fn test_format_heartbeat_reports_whole_minutes() {
+        assert_eq!(format_heartbeat(Duration::from_secs(185)), "still running, 3m elapsed");
+    }
+}
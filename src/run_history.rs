@@ -0,0 +1,106 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use crate::atomic_file::atomic_write;
+
+pub const DEFAULT_MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    pub duration_secs: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RunHistory {
+    #[serde(default)]
+    runs: Vec<RunRecord>,
+}
+
+pub fn history_path(root_dir: &Path) -> PathBuf {
+    root_dir.join(".overcode").join("run_history.toml")
+}
+
+fn read_history(path: &Path) -> anyhow::Result<RunHistory> {
+    if !path.exists() {
+        return Ok(RunHistory::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read run history: {}", path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse run history: {}", path.display()))
+}
+
+pub fn append_record(path: &Path, record: RunRecord, max_entries: usize) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create run history directory: {}", parent.display()))?;
+    }
+
+    let lock_path = path.with_extension("toml.lock");
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open run history lock: {}", lock_path.display()))?;
+    lock.lock()
+        .with_context(|| format!("Failed to lock run history: {}", lock_path.display()))?;
+
+    let mut history = read_history(path)?;
+    history.runs.push(record);
+    let excess = history.runs.len().saturating_sub(max_entries);
+    history.runs.drain(..excess);
+
+    let content = toml::to_string(&history).context("Failed to serialize run history")?;
+    atomic_write(path, content)
+        .with_context(|| format!("Failed to write run history: {}", path.display()))?;
+
+    Ok(())
+}
+
+pub fn read_last(path: &Path, count: usize) -> anyhow::Result<Vec<RunRecord>> {
+    let history = read_history(path)?;
+    let skip = history.runs.len().saturating_sub(count);
+    Ok(history.runs.into_iter().skip(skip).collect())
+}
+
+pub fn format_history(records: &[RunRecord]) -> String {
+    let mut table = format!("{:>10}  {:>4}  {:>9}  COMMAND\n", "TIMESTAMP", "EXIT", "DURATION");
+    for record in records {
+        let exit_code = record
+            .exit_code
+            .map_or_else(|| "-".to_string(), |code| code.to_string());
+        let command_line = std::iter::once(&record.command)
+            .chain(&record.args)
+            .chain(&record.extra_args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let image = record
+            .image
+            .as_ref()
+            .map(|image| format!("  ({})", image))
+            .unwrap_or_default();
+        table.push_str(&format!(
+            "{:>10}  {:>4}  {:>8.2}s  {}{}\n",
+            record.timestamp, exit_code, record.duration_secs, command_line, image
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+#[path = "run_history/driver/history/history.rs"]
+mod driver_history_history;
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::TempDir;
+    use crate::run_history::{append_record, format_history, history_path, read_last, RunRecord};
+
+    fn record(timestamp: u64, exit_code: Option<i32>) -> RunRecord {
+        RunRecord {
+            timestamp,
+            command: "cargo".to_string(),
+            args: vec!["run".to_string()],
+            image: None,
+            extra_args: vec!["--port".to_string(), "8080".to_string()],
+            exit_code,
+            duration_secs: 1.5,
+        }
+    }
+
+    #[test]
+    fn test_append_and_read_last_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = history_path(temp_dir.path());
+
+        for timestamp in 1..=3 {
+            append_record(&path, record(timestamp, Some(0)), 10).unwrap();
+        }
+
+        let last_two = read_last(&path, 2).unwrap();
+        assert_eq!(last_two, vec![record(2, Some(0)), record(3, Some(0))]);
+        assert_eq!(read_last(&path, 10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_append_truncates_oldest_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = history_path(temp_dir.path());
+
+        for timestamp in 1..=5 {
+            append_record(&path, record(timestamp, Some(0)), 3).unwrap();
+        }
+
+        let timestamps: Vec<u64> = read_last(&path, 10).unwrap().iter().map(|r| r.timestamp).collect();
+        assert_eq!(timestamps, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_read_last_without_history() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert!(read_last(&history_path(temp_dir.path()), 5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_every_record() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Arc::new(history_path(temp_dir.path()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|timestamp| {
+                let path = Arc::clone(&path);
+                thread::spawn(move || append_record(&path, record(timestamp, Some(0)), 100).unwrap())
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let mut timestamps: Vec<u64> = read_last(&path, 100).unwrap().iter().map(|r| r.timestamp).collect();
+        timestamps.sort();
+        assert_eq!(timestamps, (0..8).collect::<Vec<_>>());
+        assert!(fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .all(|entry| !entry.unwrap().path().to_string_lossy().ends_with(".tmp")));
+    }
+
+    #[test]
+    fn test_format_history_table() {
+        let mut with_image = record(1_700_000_000, None);
+        with_image.image = Some("docker.io/library/rust:latest".to_string());
+
+        let table = format_history(&[record(1_700_000_100, Some(1)), with_image]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], " TIMESTAMP  EXIT   DURATION  COMMAND");
+        assert_eq!(lines[1], "1700000100     1      1.50s  cargo run --port 8080");
+        assert_eq!(
+            lines[2],
+            "1700000000     -      1.50s  cargo run --port 8080  (docker.io/library/rust:latest)"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::{Condvar, Mutex};
+use log::debug;
+
+struct SemaphoreState {
+    limit: usize,
+    in_use: usize,
+}
+
+pub struct Semaphore {
+    state: Mutex<SemaphoreState>,
+    released: Condvar,
+}
+
+pub struct Permit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Semaphore {
+    pub const fn new(limit: usize) -> Self {
+        Self {
+            state: Mutex::new(SemaphoreState { limit, in_use: 0 }),
+            released: Condvar::new(),
+        }
+    }
+
+    pub fn set_limit(&self, limit: usize) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.limit = limit.max(1);
+        self.released.notify_all();
+    }
+
+    pub fn acquire(&self) -> Permit<'_> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        while state.in_use >= state.limit {
+            state = self.released.wait(state).unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.in_use += 1;
+        Permit { semaphore: self }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.semaphore.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.in_use -= 1;
+        self.semaphore.released.notify_one();
+    }
+}
+
+static PODMAN_SLOTS: Semaphore = Semaphore::new(usize::MAX);
+
+fn resolve_limit(jobs: Option<usize>, max_parallel: Option<usize>) -> usize {
+    jobs.or(max_parallel)
+        .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+        .max(1)
+}
+
+pub fn configure(jobs: Option<usize>, max_parallel: Option<usize>) {
+    let limit = resolve_limit(jobs, max_parallel);
+    debug!("Running up to {} podman operation(s) in parallel", limit);
+    PODMAN_SLOTS.set_limit(limit);
+}
+
+pub fn acquire() -> Permit<'static> {
+    PODMAN_SLOTS.acquire()
+}
+
+#[cfg(test)]
+#[path = "scheduler/driver/semaphore/semaphore.rs"]
+mod driver_semaphore_semaphore;
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+    use crate::scheduler::{resolve_limit, Semaphore};
+
+    fn peak_concurrency(semaphore: &Semaphore, workers: usize) -> usize {
+        let current = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    let _permit = semaphore.acquire();
+                    let running = current.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(running, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    current.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn test_semaphore_caps_peak_concurrency() {
+        let semaphore = Semaphore::new(3);
+
+        let peak = peak_concurrency(&semaphore, 12);
+
+        assert!(peak <= 3, "peak concurrency was {}", peak);
+        assert!(peak >= 2, "workers never overlapped (peak {})", peak);
+    }
+
+    #[test]
+    fn test_semaphore_limit_of_one_serializes_workers() {
+        let semaphore = Semaphore::new(1);
+
+        assert_eq!(peak_concurrency(&semaphore, 6), 1);
+    }
+
+    #[test]
+    fn test_semaphore_set_limit_applies_to_later_workers() {
+        let semaphore = Semaphore::new(usize::MAX);
+        semaphore.set_limit(2);
+
+        assert!(peak_concurrency(&semaphore, 8) <= 2);
+    }
+
+    #[test]
+    fn test_resolve_limit_prefers_cli_jobs() {
+        assert_eq!(resolve_limit(Some(2), Some(8)), 2);
+        assert_eq!(resolve_limit(None, Some(8)), 8);
+        assert_eq!(resolve_limit(Some(0), None), 1);
+        assert!(resolve_limit(None, None) >= 1);
+    }
+}
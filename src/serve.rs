@@ -0,0 +1,141 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixListener;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+use crate::config::Config;
+use crate::test::{self, TestOptions};
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Request {
+    Status,
+    Reload,
+    Deps { path: String },
+    Test {
+        #[serde(default)]
+        filter: Option<String>,
+    },
+}
+
+pub struct ServeState {
+    config: Config,
+    config_path: PathBuf,
+    root_dir: PathBuf,
+    options: TestOptions,
+}
+
+impl ServeState {
+    pub fn new(config: Config, config_path: &Path, root_dir: &Path, options: TestOptions) -> Self {
+        Self {
+            config,
+            config_path: config_path.to_path_buf(),
+            root_dir: root_dir.to_path_buf(),
+            options,
+        }
+    }
+}
+
+pub fn socket_path(root_dir: &Path) -> PathBuf {
+    root_dir.join(".overcode").join("serve.sock")
+}
+
+fn handle_request(state: &mut ServeState, request: Request) -> Result<Value> {
+    match request {
+        Request::Status => {
+            let index = test::index_drivers(&state.config, &state.root_dir)?;
+            Ok(json!({
+                "ok": true,
+                "root_dir": state.root_dir,
+                "drivers": index.drivers.len(),
+                "mocks": index.mocks.len(),
+            }))
+        }
+        Request::Reload => {
+            state.config = Config::load(&state.config_path)?;
+            Ok(json!({ "ok": true }))
+        }
+        Request::Deps { path } => {
+            let index = test::index_drivers(&state.config, &state.root_dir)?;
+            Ok(json!({
+                "ok": true,
+                "path": path,
+                "drivers": index.affected_drivers(&path),
+            }))
+        }
+        Request::Test { filter } => {
+            let options = TestOptions { driver_filter: filter, ..state.options.clone() };
+            let report = test::process_test_report(&state.config, &state.root_dir, &options)?;
+            Ok(json!({
+                "ok": true,
+                "passed": test::check_driver_results(&report.results, &options).is_ok(),
+                "counts": { "passed": report.passed, "failed": report.failed },
+                "results": report.results.iter().map(test::DriverResult::to_json).collect::<Vec<_>>(),
+            }))
+        }
+    }
+}
+
+fn handle_line(state: &mut ServeState, line: &str) -> Value {
+    let response = serde_json::from_str::<Request>(line)
+        .with_context(|| format!("Invalid request: {}", line))
+        .and_then(|request| handle_request(state, request));
+
+    response.unwrap_or_else(|err| json!({ "ok": false, "error": format!("{:#}", err) }))
+}
+
+pub fn serve_connection(state: &mut ServeState, reader: impl BufRead, mut writer: impl Write) -> Result<()> {
+    for line in reader.lines() {
+        let line = line.context("Failed to read request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = handle_line(state, &line);
+        writeln!(writer, "{}", response).context("Failed to write response")?;
+        writer.flush().context("Failed to write response")?;
+    }
+    Ok(())
+}
+
+pub fn process_serve(state: &mut ServeState) -> Result<()> {
+    let path = socket_path(&state.root_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    if path.exists() {
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove stale socket: {}", path.display()))?;
+    }
+    let listener = UnixListener::bind(&path)
+        .with_context(|| format!("Failed to listen on {}", path.display()))?;
+    info!("Listening on {}", path.display());
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        let reader = match stream.try_clone() {
+            Ok(reader) => BufReader::new(reader),
+            Err(e) => {
+                warn!("Failed to accept connection: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = serve_connection(state, reader, &stream) {
+            warn!("Connection closed with error: {:#}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "serve/driver/protocol/protocol.rs"]
+mod driver_protocol_protocol;
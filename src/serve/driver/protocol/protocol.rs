@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use serde_json::{json, Value};
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::serve::{serve_connection, ServeState};
+    use crate::test::TestOptions;
+
+    const CONFIG: &str = r#"
+[[driver_patterns]]
+pattern = "src/([^/]+)/driver/([^/]+)\\.sh"
+testcase = "$1_$2"
+
+[[mock_patterns]]
+pattern = "src/([^/]+)/mock/([^/]+)\\.sh"
+testcase = "$1_$2"
+mount_path = "src/$1.sh"
+
+[command.test]
+command = "sh"
+args = ["{driver_file}"]
+"#;
+
+    fn setup() -> (TempDir, ServeState) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, CONFIG).unwrap();
+        for file in ["src/a/driver/x.sh", "src/a/mock/x.sh", "src/b/driver/y.sh"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let config = Config::load(&config_path).unwrap();
+        let state = ServeState::new(config, &config_path, temp_dir.path(), TestOptions::default());
+        (temp_dir, state)
+    }
+
+    fn exchange(state: &mut ServeState, requests: &[&str]) -> Vec<Value> {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        std::thread::scope(|scope| {
+            let reader = BufReader::new(server.try_clone().unwrap());
+            let worker = scope.spawn(move || serve_connection(state, reader, &server));
+            for request in requests {
+                writeln!(client, "{}", request).unwrap();
+            }
+            client.shutdown(std::net::Shutdown::Write).unwrap();
+            worker.join().unwrap().unwrap();
+        });
+        BufReader::new(client)
+            .lines()
+            .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_status_and_deps_requests() {
+        let (_temp_dir, mut state) = setup();
+
+        let responses = exchange(&mut state, &[
+            r#"{"op":"status"}"#,
+            r#"{"op":"deps","path":"src/a.sh"}"#,
+            r#"{"op":"deps","path":"src/a/mock/x.sh"}"#,
+            r#"{"op":"deps","path":"src/b/driver/y.sh"}"#,
+            r#"{"op":"deps","path":"README.md"}"#,
+        ]);
+
+        assert_eq!(responses.len(), 5);
+        assert_eq!(responses[0]["ok"], json!(true));
+        assert_eq!(responses[0]["drivers"], json!(2));
+        assert_eq!(responses[0]["mocks"], json!(1));
+        assert_eq!(responses[1]["drivers"], json!(["src/a/driver/x.sh"]));
+        assert_eq!(responses[2]["drivers"], json!(["src/a/driver/x.sh"]));
+        assert_eq!(responses[3]["drivers"], json!(["src/b/driver/y.sh"]));
+        assert_eq!(responses[4]["drivers"], json!([]));
+    }
+
+    #[test]
+    fn test_test_request_runs_filtered_drivers() {
+        let (_temp_dir, mut state) = setup();
+
+        let responses = exchange(&mut state, &[r#"{"op":"test","filter":"src/b/"}"#]);
+
+        let results = responses[0]["results"].as_array().unwrap();
+        assert_eq!(responses[0]["ok"], json!(true));
+        assert_eq!(responses[0]["passed"], json!(false));
+        assert_eq!(responses[0]["counts"], json!({ "passed": 0, "failed": 1 }));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["driver_file"], json!("src/b/driver/y.sh"));
+        assert_eq!(results[0]["status"], json!("FAIL"));
+        assert_eq!(results[0]["disposition"], json!("ran"));
+        assert_eq!(results[1]["driver_file"], json!("src/a/driver/x.sh"));
+        assert_eq!(results[1]["disposition"], json!("skipped_filter"));
+    }
+
+    #[test]
+    fn test_invalid_requests_return_errors_and_keep_serving() {
+        let (_temp_dir, mut state) = setup();
+
+        let responses = exchange(&mut state, &[
+            "not json",
+            r#"{"op":"unknown"}"#,
+            "",
+            r#"{"op":"status"}"#,
+        ]);
+
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["ok"], json!(false));
+        assert!(responses[0]["error"].as_str().unwrap().contains("Invalid request"));
+        assert_eq!(responses[1]["ok"], json!(false));
+        assert_eq!(responses[2]["ok"], json!(true));
+    }
+
+    #[test]
+    fn test_reload_picks_up_config_changes() {
+        let (temp_dir, mut state) = setup();
+        fs::write(temp_dir.path().join("overcode.toml"), "[command.test]\ncommand = \"sh\"\nargs = []\n").unwrap();
+
+        let responses = exchange(&mut state, &[
+            r#"{"op":"status"}"#,
+            r#"{"op":"reload"}"#,
+            r#"{"op":"status"}"#,
+        ]);
+
+        assert_eq!(responses[0]["drivers"], json!(2));
+        assert_eq!(responses[1]["ok"], json!(true));
+        assert_eq!(responses[2]["drivers"], json!(0));
+    }
+}
@@ -1,62 +1,430 @@
 use anyhow::Context;
 use filetime::{set_file_mtime, FileTime};
+use globset::{GlobBuilder, GlobMatcher, GlobSetBuilder};
 use ignore::WalkBuilder;
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::SystemTime;
-use crate::config::Config;
+use std::process::{Child, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+use crate::artifact;
+use crate::config::{Config, ContainerMode, InvalidConfig, MappingEntry, ReplaceTarget, RunTestConfig};
+use crate::config_snapshot;
+use crate::env_file;
+use crate::failure_excerpt;
+use crate::log_gc;
+use crate::podman_command;
+use crate::podman_container::{self, SharedContainer};
 use crate::podman_mount;
-use log::{info, warn};
+use crate::replace_rule;
+use crate::scheduler;
+use log::{debug, info, warn};
 
-fn find_driver_matched_files(config: &Config, root_dir: &Path) -> anyhow::Result<Vec<String>> {
-    let mut builder = WalkBuilder::new(root_dir);
-    builder
-        .hidden(false)
-        .git_ignore(false)
-        .git_exclude(true);
-    
-    let walker = builder.build();
-    
-    let mut compiled_patterns = Vec::new();
-    for mapping in &config.driver_patterns {
-        let pattern = Regex::new(&mapping.pattern)
-            .with_context(|| format!("Invalid regex pattern: {}", mapping.pattern))?;
-        compiled_patterns.push(pattern);
+const SLOWEST_CALLOUT_THRESHOLD: usize = 10;
+const SLOWEST_CALLOUT_COUNT: usize = 5;
+
+#[derive(Debug)]
+pub struct TestsFailed(pub String);
+
+impl fmt::Display for TestsFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
     }
-    
-    let mut matched_files = Vec::new();
-    
-    for result in walker {
-        let entry = result?;
-        let path = entry.path();
-        
-        if !path.is_file() {
+}
+
+impl std::error::Error for TestsFailed {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverStatus {
+    Passed,
+    Failed,
+    NotRun,
+    ConfigError,
+    Excluded,
+}
+
+impl DriverStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Passed => "PASS",
+            Self::Failed => "FAIL",
+            Self::NotRun => "SKIP",
+            Self::ConfigError => "CONFIG",
+            Self::Excluded => "EXCL",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriverDisposition {
+    Ran,
+    SkippedFilter,
+    SkippedTag,
+    SkippedExcluded,
+    SkippedDeadline,
+    FailedPlanning,
+}
+
+impl DriverDisposition {
+    const ALL: [Self; 6] = [
+        Self::Ran,
+        Self::SkippedFilter,
+        Self::SkippedTag,
+        Self::SkippedExcluded,
+        Self::SkippedDeadline,
+        Self::FailedPlanning,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Ran => "ran",
+            Self::SkippedFilter => "skipped_filter",
+            Self::SkippedTag => "skipped_tag",
+            Self::SkippedExcluded => "skipped_excluded",
+            Self::SkippedDeadline => "skipped_deadline",
+            Self::FailedPlanning => "failed_planning",
+        }
+    }
+
+    pub fn reason(self) -> &'static str {
+        match self {
+            Self::Ran => "ran",
+            Self::SkippedFilter => "driver filter",
+            Self::SkippedTag => "tag filter",
+            Self::SkippedExcluded => "excluded (config)",
+            Self::SkippedDeadline => "deadline exceeded",
+            Self::FailedPlanning => "planning failed",
+        }
+    }
+
+    fn is_selected(self) -> bool {
+        !matches!(self, Self::SkippedFilter | Self::SkippedTag)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DriverResult {
+    pub driver_file: String,
+    pub testcase: Option<String>,
+    pub status: DriverStatus,
+    pub disposition: DriverDisposition,
+    pub duration: Duration,
+    pub failure_excerpt: Option<String>,
+    pub log_path: Option<PathBuf>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+}
+
+impl DriverResult {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "driver_file": self.driver_file,
+            "testcase": self.testcase,
+            "status": self.status.label(),
+            "disposition": self.disposition.key(),
+            "duration_secs": self.duration.as_secs_f64(),
+            "log_path": self.log_path,
+            "stdout": self.stdout,
+            "stderr": self.stderr,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Tap,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        match name {
+            "human" => Ok(Self::Human),
+            "tap" => Ok(Self::Tap),
+            _ => anyhow::bail!("Unknown output format: {}. Use 'human' or 'tap'", name),
+        }
+    }
+}
+
+fn format_tap(results: &[DriverResult], snapshot: &str) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", results.len());
+    for line in snapshot.lines() {
+        tap.push_str(&format!("# {}\n", line));
+    }
+    for (index, result) in results.iter().enumerate() {
+        let name = result.testcase.as_deref().unwrap_or(&result.driver_file);
+        match result.status {
+            DriverStatus::Passed => tap.push_str(&format!("ok {} - {}\n", index + 1, name)),
+            DriverStatus::NotRun | DriverStatus::Excluded => {
+                tap.push_str(&format!("ok {} - {} # SKIP {}\n", index + 1, name, result.disposition.reason()));
+            }
+            DriverStatus::Failed | DriverStatus::ConfigError => {
+                let directive = if result.status == DriverStatus::ConfigError { " # configuration error" } else { "" };
+                tap.push_str(&format!("not ok {} - {}{}\n", index + 1, name, directive));
+                tap.push_str("  ---\n");
+                tap.push_str(&format!("  driver_file: {}\n", result.driver_file));
+                tap.push_str(&format!("  duration_ms: {}\n", result.duration.as_millis()));
+                if let Some(ref stderr) = result.stderr {
+                    tap.push_str("  stderr: |\n");
+                    for line in stderr.lines() {
+                        tap.push_str(&format!("    {}\n", line));
+                    }
+                }
+                tap.push_str("  ...\n");
+            }
+        }
+    }
+    tap
+}
+
+fn write_tap(results: &[DriverResult], snapshot: &str, options: &TestOptions) -> anyhow::Result<()> {
+    if options.format == OutputFormat::Tap {
+        std::io::stdout().write_all(format_tap(results, snapshot).as_bytes())
+            .context("Failed to write TAP output")?;
+    }
+    Ok(())
+}
+
+fn format_summary_table(results: &[DriverResult]) -> String {
+    let mut sorted: Vec<&DriverResult> = results.iter().filter(|result| result.disposition.is_selected()).collect();
+    sorted.sort_by(|a, b| {
+        b.duration
+            .cmp(&a.duration)
+            .then_with(|| a.driver_file.cmp(&b.driver_file))
+    });
+
+    let durations: Vec<String> = sorted
+        .iter()
+        .map(|result| match result.status {
+            DriverStatus::NotRun | DriverStatus::ConfigError | DriverStatus::Excluded => "-".to_string(),
+            _ => format!("{:.2}s", result.duration.as_secs_f64()),
+        })
+        .collect();
+    let duration_width = durations
+        .iter()
+        .map(String::len)
+        .chain(std::iter::once("DURATION".len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut table = format!("{:<6}  {:>width$}  DRIVER\n", "STATUS", "DURATION", width = duration_width);
+    for (result, duration) in sorted.iter().zip(&durations) {
+        table.push_str(&format!(
+            "{:<6}  {:>width$}  {}\n",
+            result.status.label(),
+            duration,
+            result.driver_file,
+            width = duration_width
+        ));
+    }
+
+    if sorted.len() > SLOWEST_CALLOUT_THRESHOLD {
+        table.push_str(&format!("Slowest {} drivers:\n", SLOWEST_CALLOUT_COUNT));
+        for (result, duration) in sorted.iter().zip(&durations).take(SLOWEST_CALLOUT_COUNT) {
+            table.push_str(&format!(
+                "  {:>width$}  {}\n",
+                duration,
+                result.driver_file,
+                width = duration_width
+            ));
+        }
+    }
+
+    let excluded_count = results.iter().filter(|result| result.status == DriverStatus::Excluded).count();
+    if excluded_count > 0 {
+        table.push_str(&format!("{} driver(s) excluded (config)\n", excluded_count));
+    }
+
+    let config_errors: Vec<&DriverResult> = results
+        .iter()
+        .filter(|result| result.status == DriverStatus::ConfigError)
+        .collect();
+    if !config_errors.is_empty() {
+        table.push_str("Configuration errors:\n");
+        for result in config_errors {
+            table.push_str(&format!(
+                "  {}: {}\n",
+                result.driver_file,
+                result.stderr.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    table
+}
+
+fn format_disposition_counts(results: &[DriverResult]) -> String {
+    DriverDisposition::ALL
+        .iter()
+        .filter_map(|disposition| {
+            let count = results.iter().filter(|result| result.disposition == *disposition).count();
+            (count > 0).then(|| format!("{} {}", count, disposition.key()))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_skipped_drivers(results: &[DriverResult]) -> String {
+    let mut listing = String::new();
+    for disposition in DriverDisposition::ALL {
+        if disposition == DriverDisposition::Ran {
             continue;
         }
-        
-        let relative_path = path.strip_prefix(root_dir)?
-            .to_string_lossy()
-            .to_string();
-        
-        for pattern in &compiled_patterns {
-            if pattern.is_match(&relative_path) {
-                matched_files.push(relative_path.clone());
-                break;
+        let drivers: Vec<&str> = results
+            .iter()
+            .filter(|result| result.disposition == disposition)
+            .map(|result| result.driver_file.as_str())
+            .collect();
+        if !drivers.is_empty() {
+            listing.push_str(&format!("{} ({}):\n", disposition.key(), disposition.reason()));
+            for driver in drivers {
+                listing.push_str(&format!("  {}\n", driver));
             }
         }
     }
-    
-    matched_files.sort();
-    matched_files.dedup();
-    
-    Ok(matched_files)
+    listing
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct MatchedFiles {
+    drivers: Vec<(String, usize)>,
+    mocks: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct ScanCache {
+    root_dir: PathBuf,
+    patterns: Vec<String>,
+    mock_patterns: Vec<String>,
+    test_ignores: Vec<String>,
+    scanned_at: Option<SystemTime>,
+    dir_mtimes: Vec<(PathBuf, SystemTime)>,
+    files: MatchedFiles,
+}
+
+impl ScanCache {
+    fn invalidate(&mut self) {
+        *self = Self::default();
+    }
+
+    fn is_fresh(&self, root_dir: &Path, patterns: &[String], mock_patterns: &[String], test_ignores: &[String]) -> bool {
+        if self.scanned_at.is_none()
+            || self.root_dir != root_dir
+            || self.patterns != patterns
+            || self.mock_patterns != mock_patterns
+            || self.test_ignores != test_ignores
+        {
+            return false;
+        }
+
+        self.dir_mtimes.iter().all(|(dir, mtime)| {
+            fs::metadata(dir)
+                .and_then(|metadata| metadata.modified())
+                .map(|current| current == *mtime)
+                .unwrap_or(false)
+        })
+    }
+}
+
+fn compile_test_ignores(test_ignores: &[String]) -> anyhow::Result<Vec<Regex>> {
+    test_ignores
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern).with_context(|| format!("Invalid test_ignores pattern: {}", pattern))
+        })
+        .collect()
+}
+
+fn is_test_ignored(relative_path: &str, test_ignores: &[Regex]) -> bool {
+    test_ignores.iter().any(|pattern| pattern.is_match(relative_path))
+}
+
+#[derive(Debug)]
+enum PathPattern {
+    Regex(Regex),
+    Glob(String, GlobMatcher),
+}
+
+impl PathPattern {
+    fn compile(mapping: &MappingEntry) -> anyhow::Result<Self> {
+        if let Some(ref glob) = mapping.glob {
+            let matcher = GlobBuilder::new(glob)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid glob pattern: {}", glob))?
+                .compile_matcher();
+            return Ok(PathPattern::Glob(glob.clone(), matcher));
+        }
+
+        let pattern = mapping.source();
+        let regex = Regex::new(pattern)
+            .with_context(|| format!("Invalid regex pattern: {}", pattern))?;
+        Ok(PathPattern::Regex(regex))
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        match self {
+            PathPattern::Regex(regex) => regex.is_match(path),
+            PathPattern::Glob(_, matcher) => matcher.is_match(path),
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        match self {
+            PathPattern::Regex(regex) => regex.as_str(),
+            PathPattern::Glob(glob, _) => glob,
+        }
+    }
 }
 
-fn find_mock_matched_files(config: &Config, root_dir: &Path) -> anyhow::Result<Vec<String>> {
+fn compile_patterns(mappings: &[MappingEntry]) -> anyhow::Result<Vec<PathPattern>> {
+    mappings.iter().map(PathPattern::compile).collect()
+}
+
+fn match_dirs(mappings: &[MappingEntry]) -> Vec<bool> {
+    mappings.iter().map(|mapping| mapping.match_dirs).collect()
+}
+
+static SCAN_CACHE: OnceLock<Mutex<ScanCache>> = OnceLock::new();
+
+fn find_matched_files(
+    driver_patterns: &[PathPattern],
+    match_dirs: &[bool],
+    mock_patterns: &[PathPattern],
+    ignore_patterns: &[Regex],
+    root_dir: &Path,
+    cache: &mut ScanCache,
+) -> anyhow::Result<MatchedFiles> {
+    let as_strings = |patterns: &[PathPattern]| -> Vec<String> {
+        patterns.iter().map(|pattern| pattern.as_str().to_string()).collect()
+    };
+    let matches_dirs = |index: usize| match_dirs.get(index).copied().unwrap_or(false);
+    let patterns: Vec<String> = as_strings(driver_patterns)
+        .into_iter()
+        .enumerate()
+        .map(|(index, pattern)| if matches_dirs(index) { format!("{} (dirs)", pattern) } else { pattern })
+        .collect();
+    let mock_pattern_keys = as_strings(mock_patterns);
+    let test_ignores: Vec<String> = ignore_patterns
+        .iter()
+        .map(|pattern| pattern.as_str().to_string())
+        .collect();
+
+    if cache.is_fresh(root_dir, &patterns, &mock_pattern_keys, &test_ignores) {
+        info!(
+            "Directory tree unchanged since last scan, reusing {} driver file(s) and {} mock file(s)",
+            cache.files.drivers.len(),
+            cache.files.mocks.len()
+        );
+        return Ok(cache.files.clone());
+    }
+    cache.invalidate();
+
     let mut builder = WalkBuilder::new(root_dir);
     builder
         .hidden(false)
@@ -65,19 +433,35 @@ fn find_mock_matched_files(config: &Config, root_dir: &Path) -> anyhow::Result<V
     
     let walker = builder.build();
     
-    let mut compiled_patterns = Vec::new();
-    for mapping in &config.mock_patterns {
-        let pattern = Regex::new(&mapping.pattern)
-            .with_context(|| format!("Invalid regex pattern: {}", mapping.pattern))?;
-        compiled_patterns.push(pattern);
-    }
-    
-    let mut matched_files = Vec::new();
+    let mut matched = MatchedFiles::default();
+    let mut dir_mtimes = Vec::new();
     
     for result in walker {
         let entry = result?;
         let path = entry.path();
         
+        if path.is_dir() {
+            let mtime = fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .with_context(|| format!("Failed to read mtime for directory: {}", path.display()))?;
+            dir_mtimes.push((path.to_path_buf(), mtime));
+
+            let relative_path = path.strip_prefix(root_dir)?
+                .to_string_lossy()
+                .to_string();
+            if relative_path.is_empty() || is_test_ignored(&relative_path, ignore_patterns) {
+                continue;
+            }
+            if let Some(index) = driver_patterns
+                .iter()
+                .enumerate()
+                .position(|(index, pattern)| matches_dirs(index) && pattern.is_match(&relative_path))
+            {
+                matched.drivers.push((relative_path, index));
+            }
+            continue;
+        }
+
         if !path.is_file() {
             continue;
         }
@@ -85,24 +469,160 @@ fn find_mock_matched_files(config: &Config, root_dir: &Path) -> anyhow::Result<V
         let relative_path = path.strip_prefix(root_dir)?
             .to_string_lossy()
             .to_string();
+        if is_test_ignored(&relative_path, ignore_patterns) {
+            continue;
+        }
         
-        for pattern in &compiled_patterns {
-            if pattern.is_match(&relative_path) {
-                matched_files.push(relative_path.clone());
-                break;
-            }
+        if let Some(index) = driver_patterns.iter().position(|pattern| pattern.is_match(&relative_path)) {
+            matched.drivers.push((relative_path.clone(), index));
+        }
+        if mock_patterns.iter().any(|pattern| pattern.is_match(&relative_path)) {
+            matched.mocks.push(relative_path);
         }
     }
     
-    matched_files.sort();
-    matched_files.dedup();
+    matched.drivers.sort();
+    matched.drivers.dedup();
+    matched.mocks.sort();
+    matched.mocks.dedup();
+
+    *cache = ScanCache {
+        root_dir: root_dir.to_path_buf(),
+        patterns,
+        mock_patterns: mock_pattern_keys,
+        test_ignores,
+        scanned_at: Some(SystemTime::now()),
+        dir_mtimes,
+        files: matched.clone(),
+    };
     
-    Ok(matched_files)
+    Ok(matched)
+}
+
+fn git_ls_files(root_dir: &Path) -> anyhow::Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(root_dir)
+        .args(["ls-files", "-z"])
+        .output()
+        .context("Failed to execute git ls-files")?;
+    if !output.status.success() {
+        anyhow::bail!("git ls-files failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+fn find_tracked_files(
+    list_files: &dyn Fn(&Path) -> anyhow::Result<Vec<String>>,
+    driver_patterns: &[PathPattern],
+    match_dirs: &[bool],
+    mock_patterns: &[PathPattern],
+    ignore_patterns: &[Regex],
+    root_dir: &Path,
+) -> Option<MatchedFiles> {
+    let files = match list_files(root_dir) {
+        Ok(files) => files,
+        Err(e) => {
+            warn!("git_tracked_only is set but tracked files could not be listed, walking {} instead: {:#}", root_dir.display(), e);
+            return None;
+        }
+    };
+    let matches_dirs = |index: usize| match_dirs.get(index).copied().unwrap_or(false);
+
+    let mut matched = MatchedFiles::default();
+    let mut dirs = BTreeSet::new();
+    for relative_path in files {
+        if !root_dir.join(&relative_path).is_file() || is_test_ignored(&relative_path, ignore_patterns) {
+            continue;
+        }
+        dirs.extend(
+            Path::new(&relative_path)
+                .ancestors()
+                .skip(1)
+                .map(|dir| dir.to_string_lossy().into_owned())
+                .filter(|dir| !dir.is_empty()),
+        );
+
+        if let Some(index) = driver_patterns.iter().position(|pattern| pattern.is_match(&relative_path)) {
+            matched.drivers.push((relative_path.clone(), index));
+        }
+        if mock_patterns.iter().any(|pattern| pattern.is_match(&relative_path)) {
+            matched.mocks.push(relative_path);
+        }
+    }
+    for dir in dirs {
+        if is_test_ignored(&dir, ignore_patterns) {
+            continue;
+        }
+        if let Some(index) = driver_patterns
+            .iter()
+            .enumerate()
+            .position(|(index, pattern)| matches_dirs(index) && pattern.is_match(&dir))
+        {
+            matched.drivers.push((dir, index));
+        }
+    }
+
+    matched.drivers.sort();
+    matched.drivers.dedup();
+    matched.mocks.sort();
+    matched.mocks.dedup();
+    Some(matched)
 }
 
-fn resolve_testcase(file_path: &str, pattern: &Regex, testcase: &str) -> Option<String> {
+fn scan_files(
+    config: &Config,
+    driver_mappings: &[MappingEntry],
+    driver_patterns: &[PathPattern],
+    mock_patterns: &[PathPattern],
+    ignore_patterns: &[Regex],
+    root_dir: &Path,
+) -> anyhow::Result<MatchedFiles> {
+    let match_dirs = match_dirs(driver_mappings);
+    if config.git_tracked_only {
+        if let Some(matched) = find_tracked_files(&git_ls_files, driver_patterns, &match_dirs, mock_patterns, ignore_patterns, root_dir) {
+            return Ok(matched);
+        }
+    }
+
+    let mut cache = SCAN_CACHE
+        .get_or_init(|| Mutex::new(ScanCache::default()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    find_matched_files(driver_patterns, &match_dirs, mock_patterns, ignore_patterns, root_dir, &mut cache)
+}
+
+fn resolve_glob_template(file_path: &str, template: &str) -> String {
+    let path = Path::new(file_path);
+    let part = |value: Option<&std::ffi::OsStr>| value.map(|v| v.to_string_lossy().into_owned()).unwrap_or_default();
+    let dir = path.parent().map(|dir| dir.to_string_lossy().into_owned()).unwrap_or_default();
+
+    template
+        .replace("{path}", file_path)
+        .replace("{stem}", &part(path.file_stem()))
+        .replace("{dir}", &dir)
+        .replace("{ext}", &part(path.extension()))
+}
+
+fn resolve_testcase(file_path: &str, pattern: &PathPattern, testcase: &str) -> Option<String> {
+    let pattern = match pattern {
+        PathPattern::Regex(regex) => regex,
+        PathPattern::Glob(_, matcher) => {
+            return matcher.is_match(file_path).then(|| resolve_glob_template(file_path, testcase));
+        }
+    };
     if let Some(captures) = pattern.captures(file_path) {
         let mut resolved = testcase.to_string();
+        for name in pattern.capture_names().flatten() {
+            if let Some(capture) = captures.name(name) {
+                resolved = resolved.replace(&format!("${{{}}}", name), capture.as_str());
+            }
+        }
         for i in 1..=captures.len() - 1 {
             if let Some(capture) = captures.get(i) {
                 let placeholder = format!("${}", i);
@@ -115,6 +635,77 @@ fn resolve_testcase(file_path: &str, pattern: &Regex, testcase: &str) -> Option<
     }
 }
 
+fn has_unresolved_placeholder(resolved: &str) -> bool {
+    resolved
+        .as_bytes()
+        .windows(2)
+        .any(|pair| pair[0] == b'$' && (pair[1].is_ascii_digit() || pair[1] == b'{'))
+}
+
+fn resolve_driver_key(
+    driver_file: &str,
+    driver_patterns_compiled: &[(&PathPattern, &String)],
+    default_template: Option<&str>,
+    require_testcase: bool,
+) -> anyhow::Result<Option<String>> {
+    let resolved = driver_patterns_compiled
+        .iter()
+        .find_map(|(pattern, testcase)| resolve_testcase(driver_file, pattern, testcase));
+    if resolved.as_deref().is_some_and(|key| !has_unresolved_placeholder(key)) {
+        return Ok(resolved);
+    }
+    if let Some(template) = default_template {
+        let key = resolve_glob_template(driver_file, template);
+        debug!("Using testcase_template for {}: {}", driver_file, key);
+        return Ok(Some(key));
+    }
+
+    let tried: Vec<String> = driver_patterns_compiled
+        .iter()
+        .map(|(pattern, testcase)| format!("{} -> {}", pattern.as_str(), testcase))
+        .collect();
+    if require_testcase {
+        anyhow::bail!(
+            "No testcase key could be resolved for {} (patterns tried: {})",
+            driver_file,
+            tried.join(", ")
+        );
+    }
+    warn!(
+        "No testcase key could be resolved for {}; running without mock context (patterns tried: {})",
+        driver_file,
+        tried.join(", ")
+    );
+    Ok(resolved)
+}
+
+fn load_driver_env(
+    driver_patterns: &[MappingEntry],
+    compiled_patterns: &[PathPattern],
+    driver_file: &str,
+    pattern_index: usize,
+    root_dir: &Path,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let (Some(mapping), Some(pattern)) = (driver_patterns.get(pattern_index), compiled_patterns.get(pattern_index)) else {
+        return Ok(Vec::new());
+    };
+    let Some(ref env_file_pattern) = mapping.env_file_pattern else {
+        return Ok(Vec::new());
+    };
+
+    let Some(env_file) = resolve_testcase(driver_file, pattern, env_file_pattern) else {
+        return Ok(Vec::new());
+    };
+
+    let env_path = root_dir.join(&env_file);
+    if !env_path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    info!("Loading driver environment from: {}", env_file);
+    env_file::load_env_file(&env_path)
+}
+
 fn refresh_mock_mtime(path: &Path) -> anyhow::Result<()> {
     let file_time = FileTime::from_system_time(SystemTime::now());
     set_file_mtime(path, file_time)
@@ -134,235 +725,1066 @@ fn restore_mock_mtime(backups: &[(PathBuf, FileTime)]) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn execute_test_command(
-    run_test: &crate::config::RunTestConfig,
-    driver_file: &str,
+fn apply_target_replace_rules(
+    input: &str,
+    rules: &[crate::config::ReplaceRule],
+    target: ReplaceTarget,
+) -> anyhow::Result<String> {
+    replace_rule::apply_replace_rules(input, rules.iter().filter(|rule| rule.replace_target == target))
+}
+
+#[derive(Debug, Clone)]
+struct MockMount {
+    mock_path: String,
+    original_path: String,
+    mock_abs_path: PathBuf,
+    original_abs_path: PathBuf,
+}
+
+type CompiledMockPattern<'a> = (&'a PathPattern, &'a String, Option<&'a str>);
+
+fn plan_mock_mounts(
+    resolved_key: &str,
+    mock_map: &HashMap<String, Vec<String>>,
+    mock_file_info: &[(String, String, Option<&str>)],
+    mock_patterns_compiled: &[CompiledMockPattern],
     root_dir: &Path,
-    mount_args: &[String],
-) -> anyhow::Result<()> {
-    let root_dir_str = root_dir.display().to_string();
-    
-    info!("Before replace_rule application: driver_file = '{}'", driver_file);
-    
-    let mut processed_driver_file = driver_file.to_string();
+    allow_conflicts: bool,
+) -> anyhow::Result<Vec<MockMount>> {
+    let mut mounts = Vec::new();
 
-    for rule in &run_test.replace_rule {
-        info!("Applying replace_rule: pattern = '{}', replace = '{}'", rule.pattern, rule.replace);
-    
-        let re = Regex::new(&rule.pattern).unwrap();
-        let replaced = re.replace(processed_driver_file.as_str(), |caps: &regex::Captures| {
-            rule.replace
-                .replace("$1", &caps[1])
-                .replace("$2", &caps[2])
-                .replace("$3", &caps[3])
+    let Some(mock_paths) = mock_map.get(resolved_key) else {
+        return Ok(mounts);
+    };
+
+    for mock_path in mock_paths {
+        let mount_path_template = mock_file_info.iter()
+            .find(|(file, key, _)| file == mock_path && key == resolved_key)
+            .and_then(|(_, _, mount_path)| *mount_path)
+            .ok_or_else(|| anyhow::anyhow!(
+                "mount_path is required for mock file: {} (matched pattern in mock_patterns)",
+                mock_path
+            ))?;
+        
+        let pattern = mock_patterns_compiled.iter()
+            .find(|(p, _, _)| p.is_match(mock_path))
+            .map(|(p, _, _)| p)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Failed to find matching pattern for mock file: {}",
+                mock_path
+            ))?;
+        
+        let original_path = resolve_testcase(mock_path, pattern, mount_path_template)
+            .ok_or_else(|| anyhow::anyhow!(
+                "Failed to capture groups from mock file path: {} with pattern",
+                mock_path
+            ))?;
+        
+        mounts.push(MockMount {
+            mock_path: mock_path.clone(),
+            mock_abs_path: root_dir.join(mock_path),
+            original_abs_path: root_dir.join(&original_path),
+            original_path,
         });
-    
-        processed_driver_file = replaced.to_string();
-        info!("After replace_rule application: '{}' -> '{}'", driver_file, processed_driver_file);
     }
+
+    mounts.sort_by(|a, b| a.mock_path.cmp(&b.mock_path));
+    check_mock_conflicts(&mounts, allow_conflicts)?;
+    if !mounts.is_empty() {
+        let listing: Vec<String> = mounts
+            .iter()
+            .map(|mount| format!("{} -> {}", mount.mock_path, mount.original_path))
+            .collect();
+        info!("Mock mounts for {}: {}", resolved_key, listing.join(", "));
+    }
+
+    Ok(mounts)
+}
+
+fn check_mock_conflicts(mounts: &[MockMount], allow_conflicts: bool) -> anyhow::Result<()> {
+    let mut claims: BTreeMap<&Path, Vec<&str>> = BTreeMap::new();
+    for mount in mounts {
+        claims
+            .entry(mount.original_abs_path.as_path())
+            .or_default()
+            .push(&mount.mock_path);
+    }
+
+    let conflicts: Vec<String> = claims
+        .into_iter()
+        .filter(|(_, mock_paths)| mock_paths.len() > 1)
+        .map(|(original_abs_path, mock_paths)| {
+            format!("{} are all mounted at {}", mock_paths.join(", "), original_abs_path.display())
+        })
+        .collect();
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+
+    let message = format!("Conflicting mock mounts:\n  {}", conflicts.join("\n  "));
+    if allow_conflicts {
+        warn!("{}", message);
+        Ok(())
+    } else {
+        anyhow::bail!("{}\nSet allow_mock_conflicts = true to only warn about this", message)
+    }
+}
+
+fn apply_mock_mounts(
+    mounts: &[MockMount],
+    touch_mocks: bool,
+    mount_args: &mut Vec<String>,
+) -> anyhow::Result<Vec<(PathBuf, FileTime)>> {
+    let mut mock_mtime_backups = Vec::new();
+
+    for mount in mounts {
+        if touch_mocks {
+            let metadata = fs::metadata(&mount.mock_abs_path).with_context(|| {
+                format!(
+                    "Failed to retrieve metadata for mock file: {}",
+                    mount.mock_abs_path.display()
+                )
+            })?;
+            let original_time = FileTime::from_last_modification_time(&metadata);
+            mock_mtime_backups.push((mount.mock_abs_path.clone(), original_time));
+            refresh_mock_mtime(&mount.mock_abs_path)?;
+        }
+        
+        mount_args.push("-v".to_string());
+        mount_args.push(format!("{}:{}:ro", 
+            mount.mock_abs_path.display(), 
+            mount.original_abs_path.display()));
+        
+        info!("Mounting mock file: {} -> {} (read-only)", mount.mock_path, mount.original_path);
+    }
+
+    Ok(mock_mtime_backups)
+}
+
+struct TestInvocation<'a> {
+    driver_file: &'a str,
+    testcase: Option<&'a str>,
+    root_dir: &'a Path,
+    run_id: &'a str,
+    mount_args: &'a [String],
+    env_vars: &'a [(String, String)],
+}
+
+struct PreparedTestCommand {
+    env_args: Vec<String>,
+    args: Vec<String>,
+}
+
+fn prepare_test_command(
+    run_test: &crate::config::RunTestConfig,
+    invocation: &TestInvocation,
+) -> anyhow::Result<PreparedTestCommand> {
+    let root_dir_str = invocation.root_dir.display().to_string();
+    
+    info!("Before replace_rule application: driver_file = '{}'", invocation.driver_file);
+    
+    let processed_driver_file =
+        apply_target_replace_rules(invocation.driver_file, &run_test.replace_rule, ReplaceTarget::DriverFile)?;
+    let processed_testcase =
+        apply_target_replace_rules(invocation.testcase.unwrap_or(""), &run_test.replace_rule, ReplaceTarget::Testcase)?;
+    
+    let driver_file_abs = invocation.root_dir.join(invocation.driver_file).display().to_string();
     
     let processed_args: Vec<String> = run_test.args
         .iter()
         .map(|arg| {
-            arg.replace("{driver_file}", &processed_driver_file)
+            arg.replace("{driver_file_abs}", &driver_file_abs)
+               .replace("{driver_file}", &processed_driver_file)
+               .replace("{testcase}", &processed_testcase)
                .replace("{root_dir}", &root_dir_str)
         })
         .collect();
     
+    let mut env_args = podman_mount::build_env_file_args(run_test.env_file.as_deref(), invocation.root_dir)?;
+    if run_test.inject_env {
+        let mut context_env = vec![
+            ("OVERCODE_DRIVER_FILE".to_string(), processed_driver_file),
+            ("OVERCODE_TESTCASE".to_string(), processed_testcase),
+        ];
+        context_env.extend(env_file::context_env(&root_dir_str, invocation.run_id));
+        env_args.extend(env_file::build_env_args(&context_env));
+    }
+    env_args.extend(env_file::build_env_args(invocation.env_vars));
+    
+    Ok(PreparedTestCommand {
+        env_args,
+        args: processed_args,
+    })
+}
+
+fn build_test_podman_args(
+    run_test: &crate::config::RunTestConfig,
+    invocation: &TestInvocation,
+    container_name: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
     let image = run_test.image
         .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("image is required in [command.test] section"))?;
+        .ok_or_else(|| InvalidConfig("image is required in [command.test] section".to_string()))?;
+    let prepared = prepare_test_command(run_test, invocation)?;
     
-    info!("Executing in podman container (image: {}): {} {:?}", image, run_test.command, processed_args);
+    info!("Executing in podman container (image: {}): {} {:?}", image, run_test.command, prepared.args);
     
-    let root_dir_str = root_dir.display().to_string();
     let mut podman_args = vec![
         "run".to_string(),
         "--rm".to_string(),
     ];
-    podman_args.extend_from_slice(mount_args);
+    if let Some(container_name) = container_name {
+        podman_args.push("--name".to_string());
+        podman_args.push(container_name.to_string());
+    }
+    podman_args.extend_from_slice(invocation.mount_args);
+    podman_args.extend(podman_mount::build_volume_mount_args(&run_test.volumes));
+    podman_args.extend(prepared.env_args);
     podman_args.push("-w".to_string());
-    podman_args.push(root_dir_str);
+    podman_args.push(invocation.root_dir.display().to_string());
     podman_args.push(image.clone());
     podman_args.push(run_test.command.clone());
-    podman_args.extend(processed_args);
+    podman_args.extend(prepared.args);
+
+    Ok(podman_args)
+}
+
+fn exec_pid_file(container_name: &str) -> String {
+    format!("/tmp/{}.pid", container_name)
+}
+
+fn build_pid_wrapper(pid_file: &str) -> Vec<String> {
+    vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        format!("echo $$ > {} && exec \"$@\"", pid_file),
+        "sh".to_string(),
+    ]
+}
+
+fn build_exec_kill_args(container_id: &str, pid_file: &str) -> Vec<String> {
+    vec![
+        "exec".to_string(),
+        container_id.to_string(),
+        "sh".to_string(),
+        "-c".to_string(),
+        format!("kill -KILL $(cat {0}) 2>/dev/null; rm -f {0}", pid_file),
+    ]
+}
+
+fn build_test_exec_args(
+    run_test: &crate::config::RunTestConfig,
+    invocation: &TestInvocation,
+    container_id: &str,
+    pid_file: Option<&str>,
+) -> anyhow::Result<Vec<String>> {
+    let prepared = prepare_test_command(run_test, invocation)?;
+    
+    info!("Executing in shared container {}: {} {:?}", container_id, run_test.command, prepared.args);
+    
+    let mut podman_args = vec!["exec".to_string()];
+    podman_args.extend(prepared.env_args);
+    podman_args.push("-w".to_string());
+    podman_args.push(invocation.root_dir.display().to_string());
+    podman_args.push(container_id.to_string());
+    if let Some(pid_file) = pid_file {
+        podman_args.extend(build_pid_wrapper(pid_file));
+    }
+    podman_args.push(run_test.command.clone());
+    podman_args.extend(prepared.args);
+
+    Ok(podman_args)
+}
+
+fn execute_test_command(
+    run_test: &crate::config::RunTestConfig,
+    invocation: &TestInvocation,
+    deadline: Option<(Instant, &str)>,
+    shared_container: Option<&str>,
+) -> anyhow::Result<CommandOutcome> {
+    let pid_file = deadline.map(|(_, container_name)| exec_pid_file(container_name));
+    let podman_args = match shared_container {
+        Some(container_id) => build_test_exec_args(run_test, invocation, container_id, pid_file.as_deref())?,
+        None => build_test_podman_args(
+            run_test,
+            invocation,
+            deadline.map(|(_, container_name)| container_name),
+        )?,
+    };
+    let image = run_test.image.as_deref().unwrap_or_default();
     
-    let output = Command::new("podman")
+    let Some((deadline_at, container_name)) = deadline else {
+        let output = podman_command::podman()
+            .args(&podman_args)
+            .output()
+                .with_context(|| format!("Failed to execute podman run for image: {}", image))?;
+        return Ok(CommandOutcome::Finished(output));
+    };
+
+    let child = podman_command::podman()
         .args(&podman_args)
-        .output()
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
             .with_context(|| format!("Failed to execute podman run for image: {}", image))?;
-    
-    std::io::stdout().write_all(&output.stdout)
-        .context("Failed to write stdout")?;
-    std::io::stderr().write_all(&output.stderr)
-        .context("Failed to write stderr")?;
-    
+
+    wait_with_deadline(child, deadline_at, || {
+        let kill_args = match (shared_container, pid_file.as_deref()) {
+            (Some(container_id), Some(pid_file)) => build_exec_kill_args(container_id, pid_file),
+            _ => vec!["kill".to_string(), container_name.to_string()],
+        };
+        let _ = podman_command::podman()
+            .args(&kill_args)
+            .output();
+    })
+}
+
+#[derive(Debug)]
+enum CommandOutcome {
+    Finished(Output),
+    DeadlineExceeded,
+}
+
+fn captured_output(outcome: &anyhow::Result<CommandOutcome>) -> (Option<String>, Option<String>) {
+    match outcome {
+        Ok(CommandOutcome::Finished(output)) => (
+            Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+            Some(String::from_utf8_lossy(&output.stderr).into_owned()),
+        ),
+        _ => (None, None),
+    }
+}
+
+fn wait_with_deadline(
+    mut child: Child,
+    deadline_at: Instant,
+    on_deadline: impl FnOnce(),
+) -> anyhow::Result<CommandOutcome> {
+    let stdout_reader = child.stdout.take().map(spawn_pipe_reader);
+    let stderr_reader = child.stderr.take().map(spawn_pipe_reader);
+
+    let status = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for test command")? {
+            break Some(status);
+        }
+        if Instant::now() >= deadline_at {
+            on_deadline();
+            let _ = child.kill();
+            child.wait().context("Failed to wait for killed test command")?;
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    let stdout = stdout_reader.map(|reader| reader.join().unwrap_or_default()).unwrap_or_default();
+    let stderr = stderr_reader.map(|reader| reader.join().unwrap_or_default()).unwrap_or_default();
+
+    Ok(match status {
+        Some(status) => CommandOutcome::Finished(Output { status, stdout, stderr }),
+        None => CommandOutcome::DeadlineExceeded,
+    })
+}
+
+fn spawn_pipe_reader(mut pipe: impl std::io::Read + Send + 'static) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+fn write_failure_log(log_dir: &Path, driver_file: &str, header: &str, output: &Output) -> anyhow::Result<PathBuf> {
+    let log_path = log_dir.join(format!("{}.log", driver_file));
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create log directory: {}", parent.display()))?;
+    }
+
+    let mut file = fs::File::create(&log_path)
+        .with_context(|| format!("Failed to create log file: {}", log_path.display()))?;
+    file.write_all(header.as_bytes())
+        .and_then(|_| file.write_all(&output.stdout))
+        .and_then(|_| file.write_all(&output.stderr))
+        .with_context(|| format!("Failed to write log file: {}", log_path.display()))?;
+
+    Ok(log_path)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+    pub check_command: bool,
+    pub filter_tags: Vec<String>,
+    pub deadline: Option<Duration>,
+    pub format: OutputFormat,
+    pub jobs: Option<usize>,
+    pub include_excluded: bool,
+    pub refresh_images: bool,
+    pub driver_filter: Option<String>,
+}
+
+type DriverFile = (String, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PlannedDriver {
+    driver_file: String,
+    pattern_index: usize,
+    disposition: DriverDisposition,
+}
+
+fn partition_excluded_drivers(
+    driver_files: Vec<DriverFile>,
+    exclude: &[String],
+) -> anyhow::Result<(Vec<DriverFile>, Vec<DriverFile>)> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in exclude {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("Invalid exclude pattern: {}", pattern))?;
+        builder.add(glob);
+    }
+    let exclude = builder.build().context("Failed to compile exclude patterns")?;
+
+    let (excluded, included): (Vec<_>, Vec<_>) = driver_files
+        .into_iter()
+        .partition(|(driver_file, _)| exclude.is_match(driver_file));
+    Ok((included, excluded))
+}
+
+fn matches_tags(pattern_index: usize, driver_patterns: &[MappingEntry], filter_tags: &[String]) -> bool {
+    filter_tags.is_empty()
+        || driver_patterns
+            .get(pattern_index)
+            .map(|mapping| mapping.tags.iter().any(|tag| filter_tags.contains(tag)))
+            .unwrap_or(false)
+}
+
+fn plan_drivers(
+    driver_files: Vec<DriverFile>,
+    driver_patterns: &[MappingEntry],
+    exclude: &[String],
+    options: &TestOptions,
+) -> anyhow::Result<Vec<PlannedDriver>> {
+    let mut skipped = Vec::new();
+    let mut selected = Vec::new();
+    for (driver_file, pattern_index) in driver_files {
+        let disposition = if !matches_tags(pattern_index, driver_patterns, &options.filter_tags) {
+            DriverDisposition::SkippedTag
+        } else if options.driver_filter.as_ref().is_some_and(|filter| !driver_file.contains(filter.as_str())) {
+            DriverDisposition::SkippedFilter
+        } else {
+            selected.push((driver_file, pattern_index));
+            continue;
+        };
+        skipped.push(PlannedDriver { driver_file, pattern_index, disposition });
+    }
+
+    let (included, excluded) = if options.include_excluded {
+        (selected, Vec::new())
+    } else {
+        partition_excluded_drivers(selected, exclude)?
+    };
+    let planned = |disposition: DriverDisposition| {
+        move |(driver_file, pattern_index): DriverFile| PlannedDriver { driver_file, pattern_index, disposition }
+    };
+    Ok(included
+        .into_iter()
+        .map(planned(DriverDisposition::Ran))
+        .chain(excluded.into_iter().map(planned(DriverDisposition::SkippedExcluded)))
+        .chain(skipped)
+        .collect())
+}
+
+fn skipped_result(driver: &PlannedDriver) -> DriverResult {
+    DriverResult {
+        driver_file: driver.driver_file.clone(),
+        testcase: None,
+        status: if driver.disposition == DriverDisposition::SkippedExcluded { DriverStatus::Excluded } else { DriverStatus::NotRun },
+        disposition: driver.disposition,
+        duration: Duration::ZERO,
+        failure_excerpt: None,
+        log_path: None,
+        stdout: None,
+        stderr: None,
+    }
+}
+
+fn build_command_check_args(image: &str, command: &str) -> Vec<String> {
+    vec![
+        "run".to_string(),
+        "--rm".to_string(),
+        image.to_string(),
+        "which".to_string(),
+        command.to_string(),
+    ]
+}
+
+fn check_command_in_image(image: &str, command: &str) -> anyhow::Result<()> {
+    info!("Checking that '{}' is available in image: {}", command, image);
+
+    let output = podman_command::podman()
+        .args(build_command_check_args(image, command))
+        .output()
+        .with_context(|| format!("Failed to execute podman run for image: {}", image))?;
+
     if !output.status.success() {
         anyhow::bail!(
-            "Test command failed with exit code: {:?}",
-            output.status.code()
+            "Command '{}' was not found in image '{}'. Use an image that provides it or change [command.test] command.",
+            command,
+            image
         );
     }
-    
+
     Ok(())
 }
 
-pub fn process_test(config_path: &Path) -> anyhow::Result<()> {
-    let config = Config::load(config_path)?;
-    let root_dir = config_path
-        .parent()
-            .ok_or_else(|| anyhow::anyhow!("Config file has no parent directory"))?;
+pub struct DriverSuite<'a> {
+    pub section: &'a str,
+    pub patterns: &'a [MappingEntry],
+    pub command: &'a RunTestConfig,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriverIndex {
+    pub drivers: Vec<(String, Option<String>)>,
+    pub mocks: Vec<(String, String, Option<String>)>,
+}
+
+impl DriverIndex {
+    pub fn affected_drivers(&self, path: &str) -> Vec<String> {
+        let keys: Vec<&str> = self.mocks
+            .iter()
+            .filter(|(mock_file, _, mount_path)| mock_file == path || mount_path.as_deref() == Some(path))
+            .map(|(_, key, _)| key.as_str())
+            .collect();
+
+        self.drivers
+            .iter()
+            .filter(|(driver_file, key)| driver_file == path || key.as_deref().is_some_and(|key| keys.contains(&key)))
+            .map(|(driver_file, _)| driver_file.clone())
+            .collect()
+    }
+}
+
+pub fn index_drivers(config: &Config, root_dir: &Path) -> anyhow::Result<DriverIndex> {
+    let root_dir = &podman_mount::canonical_root_dir(root_dir)?;
+    let mock_patterns = compile_patterns(&config.mock_patterns)?;
+    let driver_patterns = compile_patterns(&config.driver_patterns)?;
+    let ignore_patterns = compile_test_ignores(&config.test_ignores)?;
+    let MatchedFiles { drivers, mocks } =
+        scan_files(config, &config.driver_patterns, &driver_patterns, &mock_patterns, &ignore_patterns, root_dir)?;
+
+    let driver_patterns_compiled: Vec<(&PathPattern, &String)> = driver_patterns
+        .iter()
+        .zip(&config.driver_patterns)
+        .map(|(pattern, mapping)| (pattern, &mapping.testcase))
+        .collect();
+    let mut index = DriverIndex::default();
+    for (driver_file, _) in drivers {
+        let key = resolve_driver_key(&driver_file, &driver_patterns_compiled, config.testcase_template.as_deref(), false)?;
+        index.drivers.push((driver_file, key));
+    }
+    for mock_file in mocks {
+        let resolved = mock_patterns.iter().zip(&config.mock_patterns).find_map(|(pattern, mapping)| {
+            let key = resolve_testcase(&mock_file, pattern, &mapping.testcase)?;
+            let mount_path = mapping
+                .mount_path
+                .as_deref()
+                .and_then(|template| resolve_testcase(&mock_file, pattern, template));
+            Some((key, mount_path))
+        });
+        if let Some((key, mount_path)) = resolved {
+            index.mocks.push((mock_file, key, mount_path));
+        }
+    }
+
+    Ok(index)
+}
+
+pub fn test_suite(config: &Config) -> anyhow::Result<DriverSuite<'_>> {
+    let run_test = config.command
+        .as_ref()
+        .and_then(|c| c.test.as_ref())
+        .ok_or_else(|| InvalidConfig("[command.test] section not found in overcode.toml".to_string()))?;
+    Ok(DriverSuite {
+        section: "command.test",
+        patterns: &config.driver_patterns,
+        command: run_test,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct TestReport {
+    pub results: Vec<DriverResult>,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl TestReport {
+    pub fn new(results: Vec<DriverResult>) -> Self {
+        let count = |statuses: &[DriverStatus]| results.iter().filter(|result| statuses.contains(&result.status)).count();
+        let passed = count(&[DriverStatus::Passed]);
+        let failed = count(&[DriverStatus::Failed, DriverStatus::ConfigError]);
+        Self { results, passed, failed }
+    }
+}
+
+pub fn process_test_report(config: &Config, root_dir: &Path, options: &TestOptions) -> anyhow::Result<TestReport> {
+    let suite = test_suite(config)?;
     
-    let mock_files = find_mock_matched_files(&config, root_dir)?;
+    let results = run_driver_suite(config, root_dir, &suite, options)?;
+    Ok(TestReport::new(results))
+}
+
+pub fn process_test(config: &Config, root_dir: &Path, options: &TestOptions) -> anyhow::Result<()> {
+    let report = process_test_report(config, root_dir, options)?;
+    check_driver_results(&report.results, options)
+}
+
+pub fn run_driver_suite(
+    config: &Config,
+    root_dir: &Path,
+    suite: &DriverSuite,
+    options: &TestOptions,
+) -> anyhow::Result<Vec<DriverResult>> {
+    let run_test = suite.command;
+    let root_dir = &podman_mount::canonical_root_dir(root_dir)?;
+    let container_env_file = podman_mount::resolve_env_file(run_test.env_file.as_deref(), root_dir)?;
+    let secret_pattern = config_snapshot::compile_secret_pattern(config.secret_env_pattern.as_deref())?;
+    let container_env = container_env_file
+        .and_then(|path| env_file::load_env_file(&path).ok())
+        .unwrap_or_default();
+    let snapshot = config_snapshot::format_snapshot(
+        suite.section,
+        run_test,
+        config.podman.as_ref(),
+        &container_env,
+        &secret_pattern,
+    );
+    let mock_patterns = compile_patterns(&config.mock_patterns)?;
+    let driver_patterns = compile_patterns(suite.patterns)?;
+    let ignore_patterns = compile_test_ignores(&config.test_ignores)?;
+    let MatchedFiles { drivers: driver_files, mocks: mock_files } =
+        scan_files(config, suite.patterns, &driver_patterns, &mock_patterns, &ignore_patterns, root_dir)?;
     let mut mock_map: HashMap<String, Vec<String>> = HashMap::new();
     
-    let mut mock_patterns_compiled = Vec::new();
-    for mapping in &config.mock_patterns {
-        let pattern = Regex::new(&mapping.pattern)
-            .with_context(|| format!("Invalid regex pattern: {}", mapping.pattern))?;
-        mock_patterns_compiled.push((pattern, &mapping.testcase, mapping.mount_path.as_deref()));
-    }
+    let mock_patterns_compiled: Vec<CompiledMockPattern> = mock_patterns
+        .iter()
+        .zip(&config.mock_patterns)
+        .map(|(pattern, mapping)| (pattern, &mapping.testcase, mapping.mount_path.as_deref()))
+        .collect();
     
     let mut mock_file_info: Vec<(String, String, Option<&str>)> = Vec::new();
     for mock_file in &mock_files {
         for (pattern, testcase, mount_path) in &mock_patterns_compiled {
             if let Some(resolved_key) = resolve_testcase(mock_file, pattern, testcase) {
-                mock_map.entry(resolved_key.clone()).or_insert_with(Vec::new).push(mock_file.clone());
+                mock_map.entry(resolved_key.clone()).or_default().push(mock_file.clone());
                 mock_file_info.push((mock_file.clone(), resolved_key, *mount_path));
                 break;
             }
         }
     }
     
-    let driver_files = find_driver_matched_files(&config, root_dir)?;
-    
-    let run_test = config.command
-        .as_ref()
-        .and_then(|c| c.test.as_ref())
-        .ok_or_else(|| anyhow::anyhow!("[command.test] section not found in overcode.toml"))?;
+    if driver_files.is_empty() {
+        warn!("No files matched the driver patterns for [{}]. Nothing to run.", suite.section);
+        write_tap(&[], &snapshot, options)?;
+        return Ok(Vec::new());
+    }
+    let planned_drivers = plan_drivers(driver_files, suite.patterns, &run_test.exclude, options)?;
+    let (driver_files, skipped_drivers): (Vec<&PlannedDriver>, Vec<&PlannedDriver>) = planned_drivers
+        .iter()
+        .partition(|driver| driver.disposition == DriverDisposition::Ran);
+    let excluded_count = skipped_drivers
+        .iter()
+        .filter(|driver| driver.disposition == DriverDisposition::SkippedExcluded)
+        .count();
+    if excluded_count > 0 {
+        info!("Skipping {} driver(s) excluded in [{}]", excluded_count, suite.section);
+    }
     
+    info!("Found {} driver file(s), {} to test", planned_drivers.len(), driver_files.len());
     if driver_files.is_empty() {
-        warn!("No files matched driver_patterns pattern. Nothing to test.");
-        return Ok(());
+        warn!("No driver files were selected for [{}]. Nothing to run.", suite.section);
+        let results: Vec<DriverResult> = skipped_drivers.into_iter().map(skipped_result).collect();
+        info!("Driver dispositions: {}", format_disposition_counts(&results));
+        debug!("Skipped drivers:\n{}", format_skipped_drivers(&results));
+        write_tap(&results, &snapshot, options)?;
+        return Ok(results);
+    }
+
+    if run_test.image.is_some() {
+        podman_command::ensure_engine(config.podman.as_ref())?;
+    }
+    if options.check_command {
+        if let Some(ref image) = run_test.image {
+            check_command_in_image(image, &run_test.command)?;
+        }
     }
     
-    info!("Found {} driver file(s) to test", driver_files.len());
+    let driver_patterns_compiled: Vec<(&PathPattern, &String)> = driver_patterns
+        .iter()
+        .zip(suite.patterns)
+        .map(|(pattern, mapping)| (pattern, &mapping.testcase))
+        .collect();
     
-    let mut driver_patterns_compiled = Vec::new();
-    for mapping in &config.driver_patterns {
-        let pattern = Regex::new(&mapping.pattern)
-            .with_context(|| format!("Invalid regex pattern: {}", mapping.pattern))?;
-        driver_patterns_compiled.push((pattern, &mapping.testcase));
-    }
+    let shared_container = match run_test.container_mode {
+        ContainerMode::Shared => {
+            let image = run_test.image
+                .as_ref()
+                .ok_or_else(|| InvalidConfig(format!("image is required in [{}] section", suite.section)))?;
+            let mut base_args = podman_mount::build_mount_args(root_dir)?;
+            base_args.extend(podman_mount::build_volume_mount_args(&run_test.volumes));
+            Some(SharedContainer::start(image, &base_args)?)
+        }
+        ContainerMode::PerTest => None,
+    };
+    let mut shared_reused = 0;
+    
+    let logs_dir = log_gc::logs_dir(root_dir);
+    let run_id = log_gc::new_run_id(&logs_dir)?;
+    let log_dir = logs_dir.join(&run_id);
     
     let mut success_count = 0;
     let mut failure_count = 0;
+    let mut results: Vec<DriverResult> = Vec::new();
+    let mut unrun_drivers: Vec<PlannedDriver> = Vec::new();
+    let deadline_at = options.deadline.map(|deadline| Instant::now() + deadline);
     
-    for driver_file in &driver_files {
+    for (index, planned) in driver_files.iter().enumerate() {
+        if deadline_at.is_some_and(|at| Instant::now() >= at) {
+            unrun_drivers.extend(driver_files[index..].iter().map(|driver| PlannedDriver {
+                disposition: DriverDisposition::SkippedDeadline,
+                ..(*driver).clone()
+            }));
+            break;
+        }
+        let (driver_file, pattern_index) = (&planned.driver_file, &planned.pattern_index);
+
         info!("Testing driver file: {}", driver_file);
         
-        let mut driver_resolved_key: Option<String> = None;
-        for (pattern, testcase) in &driver_patterns_compiled {
-            if let Some(resolved) = resolve_testcase(driver_file, pattern, testcase) {
-                driver_resolved_key = Some(resolved);
-                break;
+        let driver_resolved_key = match resolve_driver_key(driver_file, &driver_patterns_compiled, config.testcase_template.as_deref(), run_test.require_testcase) {
+            Ok(key) => key,
+            Err(e) => {
+                warn!("✗ Configuration error for {}: {:#}", driver_file, e);
+                results.push(DriverResult {
+                    driver_file: driver_file.clone(),
+                    testcase: None,
+                    status: DriverStatus::ConfigError,
+                    disposition: DriverDisposition::FailedPlanning,
+                    duration: Duration::ZERO,
+                    failure_excerpt: None,
+                    log_path: None,
+                    stdout: None,
+                    stderr: Some(format!("{:#}", e)),
+                });
+                continue;
             }
-        }
+        };
         
-        let mut mount_args = podman_mount::build_mount_args(root_dir);
-        let mut mock_mtime_backups: Vec<(PathBuf, FileTime)> = Vec::new();
+        let mut mount_args = podman_mount::build_mount_args(root_dir)?;
         
-        if let Some(ref resolved_key) = driver_resolved_key {
-            if let Some(mock_paths) = mock_map.get(resolved_key) {
-                for mock_path in mock_paths {
-                    let mount_path_template = mock_file_info.iter()
-                        .find(|(file, key, _)| file == mock_path && key == resolved_key)
-                        .and_then(|(_, _, mount_path)| *mount_path)
-                        .ok_or_else(|| anyhow::anyhow!(
-                            "mount_path is required for mock file: {} (matched pattern in mock_patterns)",
-                            mock_path
-                        ))?;
-                    
-                    let pattern = mock_patterns_compiled.iter()
-                        .find(|(p, _, _)| p.is_match(mock_path))
-                        .map(|(p, _, _)| p)
-                        .ok_or_else(|| anyhow::anyhow!(
-                            "Failed to find matching pattern for mock file: {}",
-                            mock_path
-                        ))?;
-                    
-                    let captures = pattern.captures(mock_path)
-                        .ok_or_else(|| anyhow::anyhow!(
-                            "Failed to capture groups from mock file path: {} with pattern",
-                            mock_path
-                        ))?;
-                    
-                    let mut original_path = mount_path_template.to_string();
-                    for i in 1..=captures.len() - 1 {
-                        if let Some(capture) = captures.get(i) {
-                            let placeholder = format!("${}", i);
-                            original_path = original_path.replace(&placeholder, capture.as_str());
-                        }
-                    }
-                    
-                    let mock_abs_path = root_dir.join(mock_path);
-                    let original_abs_path = root_dir.join(&original_path);
-
-                    let metadata = fs::metadata(&mock_abs_path).with_context(|| {
-                        format!(
-                            "Failed to retrieve metadata for mock file: {}",
-                            mock_abs_path.display()
-                        )
-                    })?;
-                    let original_time = FileTime::from_last_modification_time(&metadata);
-                    mock_mtime_backups.push((mock_abs_path.clone(), original_time));
-                    refresh_mock_mtime(&mock_abs_path)?;
-                    
-                    mount_args.push("-v".to_string());
-                    mount_args.push(format!("{}:{}:ro", 
-                        mock_abs_path.display(), 
-                        original_abs_path.display()));
-                    
-                    info!("Mounting mock file: {} -> {} (read-only)", mock_path, original_path);
-                }
+        let planned_mounts = match driver_resolved_key {
+            Some(ref resolved_key) => plan_mock_mounts(
+                resolved_key,
+                &mock_map,
+                &mock_file_info,
+                &mock_patterns_compiled,
+                root_dir,
+                run_test.allow_mock_conflicts,
+            ),
+            None => Ok(Vec::new()),
+        };
+        let mock_mounts = match planned_mounts {
+            Ok(mounts) => mounts,
+            Err(e) => {
+                warn!("✗ Test failed for {}: {:#}", driver_file, e);
+                failure_count += 1;
+                results.push(DriverResult {
+                    driver_file: driver_file.clone(),
+                    testcase: driver_resolved_key,
+                    status: DriverStatus::Failed,
+                    disposition: DriverDisposition::FailedPlanning,
+                    duration: Duration::ZERO,
+                    failure_excerpt: None,
+                    log_path: None,
+                    stdout: None,
+                    stderr: Some(format!("{:#}", e)),
+                });
+                continue;
             }
-        }
+        };
+        let mock_mtime_backups = apply_mock_mounts(&mock_mounts, run_test.touch_mocks, &mut mount_args)?;
         
-        let command_result = execute_test_command(
-            &run_test,
-            driver_file,
-            root_dir,
-            &mount_args,
-        );
+        let shared_container_id = match shared_container {
+            Some(ref container) if mock_mounts.is_empty() => {
+                shared_reused += 1;
+                Some(container.id())
+            }
+            Some(_) => {
+                info!("{} needs mock mounts, running it in a per-test container", driver_file);
+                None
+            }
+            None => None,
+        };
+        
+        let container_name = format!("overcode-test-{}-{}", std::process::id(), index);
+        let _permit = scheduler::acquire();
+        let started_at = Instant::now();
+        let mut driver_env = Vec::new();
+        let command_result = load_driver_env(suite.patterns, &driver_patterns, driver_file, *pattern_index, root_dir)
+            .and_then(|env_vars| {
+                driver_env = env_vars;
+                let invocation = TestInvocation {
+                    driver_file,
+                    testcase: driver_resolved_key.as_deref(),
+                    root_dir,
+                    run_id: &run_id,
+                    mount_args: &mount_args,
+                    env_vars: &driver_env,
+                };
+                execute_test_command(
+                    run_test,
+                    &invocation,
+                    deadline_at.map(|at| (at, container_name.as_str())),
+                    shared_container_id,
+                )
+            });
+
+        let duration = started_at.elapsed();
 
         restore_mock_mtime(&mock_mtime_backups)?;
 
-        match command_result {
-            Ok(_) => {
-                info!("✓ Test passed for: {}", driver_file);
+        let mut failure_excerpt = None;
+        let mut log_path = None;
+        let (stdout, mut stderr) = captured_output(&command_result);
+        let status = match command_result {
+            Ok(CommandOutcome::Finished(output)) if output.status.success() => {
+                if options.format == OutputFormat::Tap {
+                    std::io::stderr().write_all(&output.stdout)
+                        .context("Failed to write stdout")?;
+                } else {
+                    std::io::stdout().write_all(&output.stdout)
+                        .context("Failed to write stdout")?;
+                }
+                std::io::stderr().write_all(&output.stderr)
+                    .context("Failed to write stderr")?;
+                info!("✓ Test passed for: {} ({:.2}s)", driver_file, duration.as_secs_f64());
                 success_count += 1;
+                DriverStatus::Passed
+            }
+            Ok(CommandOutcome::Finished(output)) => {
+                let mut header = snapshot.clone();
+                for (key, value) in config_snapshot::redact_env(&driver_env, &secret_pattern) {
+                    header.push_str(&format!("driver env: {}={}\n", key, value));
+                }
+                header.push('\n');
+                let path = write_failure_log(&log_dir, driver_file, &header, &output)?;
+                let combined = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+                warn!(
+                    "✗ Test failed for {} ({:.2}s): Test command failed with exit code: {:?} (full output: {})",
+                    driver_file,
+                    duration.as_secs_f64(),
+                    output.status.code(),
+                    path.display()
+                );
+                failure_excerpt = Some(failure_excerpt::extract_excerpt(&combined));
+                log_path = Some(path);
+                failure_count += 1;
+                DriverStatus::Failed
+            }
+            Ok(CommandOutcome::DeadlineExceeded) => {
+                warn!("✗ Test killed for {} ({:.2}s): run deadline exceeded", driver_file, duration.as_secs_f64());
+                stderr = Some("run deadline exceeded".to_string());
+                failure_count += 1;
+                DriverStatus::Failed
             }
             Err(e) => {
-                warn!("✗ Test failed for {}: {}", driver_file, e);
+                warn!("✗ Test failed for {} ({:.2}s): {}", driver_file, duration.as_secs_f64(), e);
+                stderr = Some(format!("{:#}", e));
                 failure_count += 1;
+                DriverStatus::Failed
             }
-        }
+        };
+
+        results.push(DriverResult {
+            driver_file: driver_file.clone(),
+            testcase: driver_resolved_key,
+            status,
+            disposition: DriverDisposition::Ran,
+            duration,
+            failure_excerpt,
+            log_path,
+            stdout,
+            stderr,
+        });
     }
+
+    if !run_test.artifacts.is_empty() {
+        let all_passed = unrun_drivers.is_empty()
+            && results.iter().all(|result| result.status == DriverStatus::Passed);
+        artifact::collect_artifacts(
+            &run_test.artifacts,
+            root_dir,
+            all_passed,
+            shared_container.as_ref().map(SharedContainer::id),
+        )?;
+    }
+
+    results.extend(unrun_drivers.iter().map(skipped_result));
+    results.extend(skipped_drivers.into_iter().map(skipped_result));
     
-    info!("Test summary: {} passed, {} failed", success_count, failure_count);
+    write_tap(&results, &snapshot, options)?;
+    
+    info!("Test results:\n{}", format_summary_table(&results));
+    for result in &results {
+        if let (Some(excerpt), Some(log_path)) = (&result.failure_excerpt, &result.log_path) {
+            warn!(
+                "Failure excerpt for {} (full output: {}):\n{}",
+                result.driver_file,
+                log_path.display(),
+                excerpt
+            );
+        }
+    }
+    if let Some(ref container) = shared_container {
+        info!("{}", podman_container::format_startup_savings(container.startup(), shared_reused));
+    }
+    info!(
+        "Test summary: {} passed, {} failed, {} not run, {} excluded (config)",
+        success_count,
+        failure_count,
+        unrun_drivers.len(),
+        excluded_count
+    );
+    info!("Driver dispositions: {}", format_disposition_counts(&results));
+    let skipped_listing = format_skipped_drivers(&results);
+    if !skipped_listing.is_empty() {
+        debug!("Skipped drivers:\n{}", skipped_listing);
+    }
     
+    Ok(results)
+}
+
+pub fn check_driver_results(results: &[DriverResult], options: &TestOptions) -> anyhow::Result<()> {
+    let unrun_drivers: Vec<&str> = results
+        .iter()
+        .filter(|result| result.disposition == DriverDisposition::SkippedDeadline)
+        .map(|result| result.driver_file.as_str())
+        .collect();
+    if !unrun_drivers.is_empty() {
+        return Err(TestsFailed(format!(
+            "Test run exceeded the deadline of {}s; {} driver(s) did not run: {}",
+            options.deadline.unwrap_or_default().as_secs(),
+            unrun_drivers.len(),
+            unrun_drivers.join(", ")
+        )).into());
+    }
+
+    let config_errors: Vec<&str> = results
+        .iter()
+        .filter(|result| result.status == DriverStatus::ConfigError)
+        .map(|result| result.driver_file.as_str())
+        .collect();
+    if !config_errors.is_empty() {
+        return Err(InvalidConfig(format!(
+            "{} driver(s) could not resolve a testcase key: {}",
+            config_errors.len(),
+            config_errors.join(", ")
+        )).into());
+    }
+
+    let failure_count = results.iter().filter(|result| result.status == DriverStatus::Failed).count();
     if failure_count > 0 {
-        anyhow::bail!("Some tests failed: {} out of {} failed", failure_count, driver_files.len());
+        let run_count = results
+            .iter()
+            .filter(|result| !matches!(result.status, DriverStatus::Excluded | DriverStatus::NotRun))
+            .count();
+        return Err(TestsFailed(format!("Some tests failed: {} out of {} failed", failure_count, run_count)).into());
     }
     
     Ok(())
 }
 
+pub fn passed_durations(results: &[DriverResult]) -> Vec<(String, Duration)> {
+    results
+        .iter()
+        .filter(|result| result.status == DriverStatus::Passed)
+        .map(|result| (result.driver_file.clone(), result.duration))
+        .collect()
+}
+
 #[cfg(test)]
 #[path = "test/driver/config/config.rs"]
 mod driver_config_config;
 
+#[cfg(test)]
+#[path = "test/driver/deadline/deadline.rs"]
+mod driver_deadline_deadline;
+
+#[cfg(test)]
+#[path = "test/driver/disposition/disposition.rs"]
+mod driver_disposition_disposition;
+
+#[cfg(test)]
+#[path = "test/driver/exclude/exclude.rs"]
+mod driver_exclude_exclude;
+
+#[cfg(test)]
+#[path = "test/driver/git_tracked/git_tracked.rs"]
+mod driver_git_tracked_git_tracked;
+
+#[cfg(test)]
+#[path = "test/driver/glob_pattern/glob_pattern.rs"]
+mod driver_glob_pattern_glob_pattern;
+
+#[cfg(test)]
+#[path = "test/driver/match_dirs/match_dirs.rs"]
+mod driver_match_dirs_match_dirs;
+
+#[cfg(test)]
+#[path = "test/driver/mock_mount/mock_mount.rs"]
+mod driver_mock_mount_mock_mount;
+
+#[cfg(test)]
+#[path = "test/driver/named_groups/named_groups.rs"]
+mod driver_named_groups_named_groups;
+
 #[cfg(test)]
 #[path = "test/driver/podman_mount/podman_mount.rs"]
 mod driver_podman_mount_podman_mount;
 
+#[cfg(test)]
+#[path = "test/driver/podman_run/podman_run.rs"]
+mod driver_podman_run_podman_run;
+
+#[cfg(test)]
+#[path = "test/driver/report/report.rs"]
+mod driver_report_report;
+
+#[cfg(test)]
+#[path = "test/driver/require_testcase/require_testcase.rs"]
+mod driver_require_testcase_require_testcase;
+
+#[cfg(test)]
+#[path = "test/driver/scan_cache/scan_cache.rs"]
+mod driver_scan_cache_scan_cache;
+
+#[cfg(test)]
+#[path = "test/driver/summary/summary.rs"]
+mod driver_summary_summary;
+
+#[cfg(test)]
+#[path = "test/driver/tap/tap.rs"]
+mod driver_tap_tap;
+
+#[cfg(test)]
+#[path = "test/driver/test_ignores/test_ignores.rs"]
+mod driver_test_ignores_test_ignores;
+
+#[cfg(test)]
+#[path = "test/driver/testcase_template/testcase_template.rs"]
+mod driver_testcase_template_testcase_template;
@@ -2,7 +2,8 @@
 mod tests {
     use std::fs;
     use tempfile::TempDir;
-    use crate::config::Config;
+    use crate::config::{Config, ReplaceTarget};
+    use crate::test::{apply_target_replace_rules, plan_drivers, DriverDisposition, TestOptions};
 
 
     #[test]
@@ -71,8 +72,8 @@ testcase = "$1/$2.$3"
         
         let config = Config::load(&config_path).unwrap();
         
-        assert_eq!(config.driver_patterns[0].pattern, "(.+)/(.+)/driver/.+.(.+)");
-        let _pattern_str: &str = &config.driver_patterns[0].pattern;
+        assert_eq!(config.driver_patterns[0].pattern.as_deref(), Some("(.+)/(.+)/driver/.+.(.+)"));
+        let _pattern_str: &str = config.driver_patterns[0].source();
     }
 
     #[test]
@@ -615,7 +616,7 @@ mount_path = "$1/$2.$3"
         use regex::Regex;
         let mut mock_patterns_compiled = Vec::new();
         for mapping in &config.mock_patterns {
-            let pattern = Regex::new(&mapping.pattern).unwrap();
+            let pattern = Regex::new(mapping.source()).unwrap();
             mock_patterns_compiled.push((pattern, &mapping.testcase, mapping.mount_path.as_deref()));
         }
         
@@ -625,5 +626,168 @@ mount_path = "$1/$2.$3"
         assert_eq!(*testcase, "$1/$2.$3");
         assert_eq!(*mount_path, Some("$1/$2.$3"));
     }
-}
 
+    #[test]
+    fn test_replace_rule_targets_testcase() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        
+        let toml_content = r#"
+[command.test]
+command = "cargo"
+args = ["test", "{testcase}"]
+replace_rule = [
+    { pattern = "(.+)_(.+)_(.+)", replace = "$1::$2::$3", replace_target = "testcase" },
+    { pattern = "src/(.+)/driver/(.+)/(.+)\\.rs", replace = "$1" }
+]
+"#;
+        fs::write(&config_path, toml_content).unwrap();
+        
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command
+            .as_ref()
+            .and_then(|c| c.test.as_ref())
+            .expect("run_test should exist");
+        
+        assert_eq!(run_test.replace_rule[0].replace_target, ReplaceTarget::Testcase);
+        assert_eq!(run_test.replace_rule[1].replace_target, ReplaceTarget::DriverFile);
+
+        let testcase = apply_target_replace_rules("podman_image_fail", &run_test.replace_rule, ReplaceTarget::Testcase).unwrap();
+        assert_eq!(testcase, "podman::image::fail");
+
+        let driver_file = apply_target_replace_rules(
+            "src/podman_image/driver/podman_image_download/fail.rs",
+            &run_test.replace_rule,
+            ReplaceTarget::DriverFile,
+        )
+        .unwrap();
+        assert_eq!(driver_file, "podman_image");
+    }
+
+    fn replace_rules(rules: &str) -> Vec<crate::config::ReplaceRule> {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, format!(r#"
+[command.test]
+command = "cargo"
+args = ["test", "{{driver_file}}"]
+replace_rule = [{}]
+"#, rules)).unwrap();
+        
+        Config::load(&config_path)
+            .unwrap()
+            .command
+            .and_then(|c| c.test)
+            .expect("run_test should exist")
+            .replace_rule
+    }
+
+    #[test]
+    fn test_replace_rule_first_match_by_default() {
+        let rules = replace_rules(r#"{ pattern = "/driver/", replace = "::" }"#);
+        
+        assert!(!rules[0].global);
+        assert!(!rules[0].case_insensitive);
+        assert_eq!(
+            apply_target_replace_rules("a/driver/b/driver/c", &rules, ReplaceTarget::DriverFile).unwrap(),
+            "a::b/driver/c"
+        );
+    }
+
+    #[test]
+    fn test_replace_rule_global_replaces_all_occurrences() {
+        let rules = replace_rules(r#"{ pattern = "/driver/([^/]+)", replace = "::$1", global = true }"#);
+        
+        assert_eq!(
+            apply_target_replace_rules("a/driver/b/driver/c", &rules, ReplaceTarget::DriverFile).unwrap(),
+            "a::b::c"
+        );
+    }
+
+    #[test]
+    fn test_replace_rule_case_insensitive() {
+        let rules = replace_rules(r#"{ pattern = "/driver/", replace = "::", case_insensitive = true }"#);
+        
+        assert_eq!(
+            apply_target_replace_rules("a/Driver/b/DRIVER/c", &rules, ReplaceTarget::DriverFile).unwrap(),
+            "a::b/DRIVER/c"
+        );
+    }
+
+    #[test]
+    fn test_replace_rule_global_and_case_insensitive() {
+        let rules = replace_rules(
+            r#"{ pattern = "/driver/", replace = "::", global = true, case_insensitive = true }"#,
+        );
+        
+        assert_eq!(
+            apply_target_replace_rules("a/Driver/b/DRIVER/c", &rules, ReplaceTarget::DriverFile).unwrap(),
+            "a::b::c"
+        );
+    }
+
+    fn tagged_config() -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "src/db/(.+)\\.rs"
+testcase = "db_$1"
+tags = ["database"]
+
+[[driver_patterns]]
+pattern = "src/net/(.+)\\.rs"
+testcase = "net_$1"
+tags = ["network"]
+
+[[driver_patterns]]
+pattern = "src/misc/(.+)\\.rs"
+testcase = "misc_$1"
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn test_filter_tags_skips_drivers_from_other_tagged_patterns() {
+        let (_temp_dir, config) = tagged_config();
+        let driver_files = vec![
+            ("src/db/users.rs".to_string(), 0),
+            ("src/misc/util.rs".to_string(), 2),
+            ("src/net/http.rs".to_string(), 1),
+        ];
+
+        let options = TestOptions { filter_tags: vec!["database".to_string()], ..TestOptions::default() };
+
+        let planned = plan_drivers(driver_files, &config.driver_patterns, &[], &options).unwrap();
+
+        let dispositions: Vec<(&str, DriverDisposition)> = planned
+            .iter()
+            .map(|driver| (driver.driver_file.as_str(), driver.disposition))
+            .collect();
+        assert_eq!(dispositions, vec![
+            ("src/db/users.rs", DriverDisposition::Ran),
+            ("src/misc/util.rs", DriverDisposition::SkippedTag),
+            ("src/net/http.rs", DriverDisposition::SkippedTag),
+        ]);
+    }
+
+    #[test]
+    fn test_filter_tags_empty_keeps_all_drivers() {
+        let (_temp_dir, config) = tagged_config();
+        let driver_files = vec![
+            ("src/db/users.rs".to_string(), 0),
+            ("src/misc/util.rs".to_string(), 2),
+        ];
+
+        let planned = plan_drivers(driver_files.clone(), &config.driver_patterns, &[], &TestOptions::default()).unwrap();
+
+        let selected: Vec<(String, usize)> = planned
+            .into_iter()
+            .filter(|driver| driver.disposition == DriverDisposition::Ran)
+            .map(|driver| (driver.driver_file, driver.pattern_index))
+            .collect();
+        assert_eq!(selected, driver_files);
+    }
+}
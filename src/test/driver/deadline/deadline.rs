@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+    use crate::test::{build_exec_kill_args, build_pid_wrapper, wait_with_deadline, CommandOutcome};
+
+    fn spawn_sleep(secs: &str) -> std::process::Child {
+        Command::new("sleep")
+            .arg(secs)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_wait_with_deadline_stops_slow_drivers() {
+        let drivers = ["slow_a", "slow_b", "slow_c"];
+        let deadline_at = Instant::now() + Duration::from_millis(300);
+        let killed = Cell::new(0);
+        let started_at = Instant::now();
+
+        let mut finished = Vec::new();
+        let mut unrun = Vec::new();
+        for (index, driver) in drivers.iter().enumerate() {
+            if Instant::now() >= deadline_at {
+                unrun.extend(drivers[index..].iter().copied());
+                break;
+            }
+            let outcome = wait_with_deadline(spawn_sleep("5"), deadline_at, || killed.set(killed.get() + 1)).unwrap();
+            if let CommandOutcome::Finished(_) = outcome {
+                finished.push(*driver);
+            }
+        }
+
+        assert!(started_at.elapsed() < Duration::from_secs(3));
+        assert!(finished.is_empty());
+        assert_eq!(killed.get(), 1);
+        assert_eq!(unrun, vec!["slow_b", "slow_c"]);
+    }
+
+    #[test]
+    fn test_wait_with_deadline_collects_output_before_deadline() {
+        let child = Command::new("sh")
+            .args(["-c", "echo out; echo err >&2"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let outcome = wait_with_deadline(child, Instant::now() + Duration::from_secs(10), || {
+            panic!("deadline should not be reached");
+        })
+        .unwrap();
+
+        match outcome {
+            CommandOutcome::Finished(output) => {
+                assert!(output.status.success());
+                assert_eq!(output.stdout, b"out\n");
+                assert_eq!(output.stderr, b"err\n");
+            }
+            CommandOutcome::DeadlineExceeded => panic!("expected command to finish"),
+        }
+    }
+
+    #[test]
+    fn test_exec_kill_script_stops_process_started_by_pid_wrapper() {
+        let temp_dir = TempDir::new().unwrap();
+        let pid_file = temp_dir.path().join("driver.pid").display().to_string();
+        let mut child = Command::new(&build_pid_wrapper(&pid_file)[0])
+            .args(&build_pid_wrapper(&pid_file)[1..])
+            .args(["sleep", "30"])
+            .spawn()
+            .unwrap();
+        let started_at = Instant::now();
+        while std::fs::read_to_string(&pid_file).map_or(true, |pid| pid.trim().is_empty()) {
+            assert!(started_at.elapsed() < Duration::from_secs(5), "pid file was never written");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let kill_args = build_exec_kill_args("container", &pid_file);
+        let killed = Command::new(&kill_args[2]).args(&kill_args[3..]).status().unwrap();
+
+        assert!(killed.success());
+        assert_eq!(child.wait().unwrap().signal(), Some(9));
+        assert!(!std::path::Path::new(&pid_file).exists());
+    }
+}
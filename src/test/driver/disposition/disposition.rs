@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::config::MappingEntry;
+    use crate::test::{
+        check_driver_results, format_disposition_counts, format_skipped_drivers, format_summary_table, format_tap,
+        plan_drivers, skipped_result, DriverDisposition, DriverResult, DriverStatus, PlannedDriver, TestOptions,
+    };
+
+    fn mapping(tags: &[&str]) -> MappingEntry {
+        MappingEntry {
+            pattern: Some("src/(.+)\\.rs".to_string()),
+            glob: None,
+            testcase: "$1".to_string(),
+            mount_path: None,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            env_file_pattern: None,
+            match_dirs: false,
+        }
+    }
+
+    fn planned(driver_file: &str, disposition: DriverDisposition) -> PlannedDriver {
+        PlannedDriver { driver_file: driver_file.to_string(), pattern_index: 0, disposition }
+    }
+
+    fn passed(driver_file: &str) -> DriverResult {
+        DriverResult {
+            driver_file: driver_file.to_string(),
+            testcase: None,
+            status: DriverStatus::Passed,
+            disposition: DriverDisposition::Ran,
+            duration: Duration::from_millis(500),
+            failure_excerpt: None,
+            log_path: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_drivers_records_a_disposition_for_every_driver() {
+        let patterns = vec![mapping(&["fast"]), mapping(&["slow"])];
+        let driver_files = vec![
+            ("src/api/login.rs".to_string(), 0),
+            ("src/api/flaky.rs".to_string(), 0),
+            ("src/db/users.rs".to_string(), 0),
+            ("src/api/big.rs".to_string(), 1),
+        ];
+        let options = TestOptions {
+            filter_tags: vec!["fast".to_string()],
+            driver_filter: Some("api".to_string()),
+            ..TestOptions::default()
+        };
+
+        let planned = plan_drivers(driver_files, &patterns, &["src/api/flaky.rs".to_string()], &options).unwrap();
+
+        let dispositions: Vec<(&str, DriverDisposition)> = planned
+            .iter()
+            .map(|driver| (driver.driver_file.as_str(), driver.disposition))
+            .collect();
+        assert_eq!(dispositions, vec![
+            ("src/api/login.rs", DriverDisposition::Ran),
+            ("src/api/flaky.rs", DriverDisposition::SkippedExcluded),
+            ("src/db/users.rs", DriverDisposition::SkippedFilter),
+            ("src/api/big.rs", DriverDisposition::SkippedTag),
+        ]);
+    }
+
+    #[test]
+    fn test_summary_reports_counts_and_skip_reasons() {
+        let results = vec![
+            passed("src/api/login.rs"),
+            skipped_result(&planned("src/api/flaky.rs", DriverDisposition::SkippedExcluded)),
+            skipped_result(&planned("src/db/users.rs", DriverDisposition::SkippedFilter)),
+            skipped_result(&planned("src/api/big.rs", DriverDisposition::SkippedTag)),
+            skipped_result(&planned("src/api/other.rs", DriverDisposition::SkippedTag)),
+        ];
+
+        assert_eq!(format_disposition_counts(&results), "1 ran, 1 skipped_filter, 2 skipped_tag, 1 skipped_excluded");
+        assert_eq!(
+            format_skipped_drivers(&results),
+            "skipped_filter (driver filter):\n  src/db/users.rs\n\
+             skipped_tag (tag filter):\n  src/api/big.rs\n  src/api/other.rs\n\
+             skipped_excluded (excluded (config)):\n  src/api/flaky.rs\n"
+        );
+
+        let table = format_summary_table(&results);
+        assert!(table.contains("src/api/flaky.rs"));
+        assert!(!table.contains("src/db/users.rs"));
+        assert!(!table.contains("src/api/big.rs"));
+        assert!(check_driver_results(&results, &TestOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_reports_carry_the_disposition() {
+        let results = vec![
+            passed("src/api/login.rs"),
+            skipped_result(&planned("src/api/big.rs", DriverDisposition::SkippedTag)),
+        ];
+
+        assert!(format_tap(&results, "").contains("ok 2 - src/api/big.rs # SKIP tag filter\n"));
+        assert_eq!(results[0].to_json()["disposition"], "ran");
+        assert_eq!(results[1].to_json()["disposition"], "skipped_tag");
+        assert_eq!(results[1].to_json()["status"], "SKIP");
+    }
+}
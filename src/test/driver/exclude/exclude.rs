@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::test::{
+        check_driver_results, format_summary_table, format_tap, partition_excluded_drivers, DriverDisposition,
+        DriverResult, DriverStatus, TestOptions,
+    };
+
+    fn drivers(files: &[&str]) -> Vec<(String, usize)> {
+        files.iter().map(|file| (file.to_string(), 0)).collect()
+    }
+
+    fn result(driver_file: &str, status: DriverStatus) -> DriverResult {
+        DriverResult {
+            driver_file: driver_file.to_string(),
+            testcase: None,
+            status,
+            disposition: match status {
+                DriverStatus::NotRun => DriverDisposition::SkippedDeadline,
+                DriverStatus::Excluded => DriverDisposition::SkippedExcluded,
+                DriverStatus::ConfigError => DriverDisposition::FailedPlanning,
+                DriverStatus::Passed | DriverStatus::Failed => DriverDisposition::Ran,
+            },
+            duration: Duration::from_millis(250),
+            failure_excerpt: None,
+            log_path: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    #[test]
+    fn test_partition_excluded_drivers_by_glob() {
+        let exclude = vec!["src/foo/driver/*.rs".to_string(), "src/bar/driver/big.rs".to_string()];
+
+        let (included, excluded) = partition_excluded_drivers(
+            drivers(&["src/bar/driver/big.rs", "src/bar/driver/small.rs", "src/foo/driver/a.rs", "src/foo/driver/nested/b.rs"]),
+            &exclude,
+        )
+        .unwrap();
+
+        assert_eq!(included, drivers(&["src/bar/driver/small.rs", "src/foo/driver/nested/b.rs"]));
+        assert_eq!(excluded, drivers(&["src/bar/driver/big.rs", "src/foo/driver/a.rs"]));
+    }
+
+    #[test]
+    fn test_partition_excluded_drivers_without_exclude() {
+        let (included, excluded) = partition_excluded_drivers(drivers(&["src/foo/driver/a.rs"]), &[]).unwrap();
+
+        assert_eq!(included, drivers(&["src/foo/driver/a.rs"]));
+        assert!(excluded.is_empty());
+    }
+
+    #[test]
+    fn test_partition_excluded_drivers_invalid_glob() {
+        let error_msg = partition_excluded_drivers(drivers(&["src/foo/driver/a.rs"]), &["src/[".to_string()])
+            .unwrap_err()
+            .to_string();
+
+        assert!(error_msg.contains("Invalid exclude pattern: src/["));
+    }
+
+    #[test]
+    fn test_excluded_drivers_reported_without_failing_run() {
+        let results = vec![
+            result("src/foo/driver/a.rs", DriverStatus::Passed),
+            result("src/foo/driver/flaky.rs", DriverStatus::Excluded),
+        ];
+
+        let table = format_summary_table(&results);
+        assert!(table.contains("EXCL           -  src/foo/driver/flaky.rs"));
+        assert!(table.contains("1 driver(s) excluded (config)\n"));
+        assert!(format_tap(&results, "").contains("ok 2 - src/foo/driver/flaky.rs # SKIP excluded (config)\n"));
+        assert!(check_driver_results(&results, &TestOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn test_failure_count_ignores_excluded_drivers() {
+        let results = vec![
+            result("src/foo/driver/a.rs", DriverStatus::Failed),
+            result("src/foo/driver/flaky.rs", DriverStatus::Excluded),
+        ];
+
+        let error_msg = check_driver_results(&results, &TestOptions::default()).unwrap_err().to_string();
+
+        assert_eq!(error_msg, "Some tests failed: 1 out of 1 failed");
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use regex::Regex;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{compile_patterns, find_tracked_files, git_ls_files, match_dirs, MatchedFiles};
+
+    fn setup() -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        for file in ["src/a/driver/x.rs", "src/a/mock/x.rs", "target/gen/driver/out.rs", "src/b/driver/skip.rs"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+git_tracked_only = true
+
+[[driver_patterns]]
+pattern = "(.+)/driver/(.+)\\.rs"
+testcase = "$2"
+
+[[mock_patterns]]
+pattern = "(.+)/mock/(.+)\\.rs"
+testcase = "$2"
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        (temp_dir, config)
+    }
+
+    fn find(
+        config: &Config,
+        root_dir: &Path,
+        ignores: &[Regex],
+        list_files: &dyn Fn(&Path) -> anyhow::Result<Vec<String>>,
+    ) -> Option<MatchedFiles> {
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+        let mock_patterns = compile_patterns(&config.mock_patterns).unwrap();
+        find_tracked_files(
+            list_files,
+            &driver_patterns,
+            &match_dirs(&config.driver_patterns),
+            &mock_patterns,
+            ignores,
+            root_dir,
+        )
+    }
+
+    #[test]
+    fn test_only_tracked_files_are_returned() {
+        let (temp_dir, config) = setup();
+        assert!(config.git_tracked_only);
+        let tracked = |_: &Path| Ok(vec![
+            "src/a/driver/x.rs".to_string(),
+            "src/a/mock/x.rs".to_string(),
+            "src/b/driver/skip.rs".to_string(),
+            "src/c/driver/deleted.rs".to_string(),
+        ]);
+        let ignores = vec![Regex::new("^src/b/").unwrap()];
+
+        let matched = find(&config, temp_dir.path(), &ignores, &tracked).unwrap();
+
+        assert_eq!(matched.drivers, vec![("src/a/driver/x.rs".to_string(), 0)]);
+        assert_eq!(matched.mocks, vec!["src/a/mock/x.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_lister_failure_falls_back_to_walk() {
+        let (temp_dir, config) = setup();
+
+        let matched = find(&config, temp_dir.path(), &[], &|_| anyhow::bail!("not a git repository"));
+
+        assert!(matched.is_none());
+    }
+
+    #[test]
+    fn test_git_ls_files_lists_paths_relative_to_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        for file in ["project/src/driver/a.rs", "project/build/out.rs"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let git = |args: &[&str]| {
+            let output = std::process::Command::new("git").arg("-C").arg(temp_dir.path()).args(args).output().unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        };
+        git(&["init", "-q"]);
+        git(&["add", "project/src/driver/a.rs"]);
+
+        assert_eq!(git_ls_files(&project).unwrap(), vec!["src/driver/a.rs".to_string()]);
+        assert!(git_ls_files(&TempDir::new().unwrap().path().join("missing")).is_err());
+    }
+}
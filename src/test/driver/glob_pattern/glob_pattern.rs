@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{Config, MappingEntry};
+    use crate::test::{compile_patterns, find_matched_files, resolve_testcase, PathPattern, ScanCache};
+
+    fn glob_entry(glob: &str, testcase: &str) -> MappingEntry {
+        MappingEntry {
+            pattern: None,
+            glob: Some(glob.to_string()),
+            testcase: testcase.to_string(),
+            mount_path: None,
+            tags: Vec::new(),
+            env_file_pattern: None,
+            match_dirs: false,
+        }
+    }
+
+    fn load(temp_dir: &TempDir, toml_content: &str) -> anyhow::Result<Config> {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        Config::load(&config_path)
+    }
+
+    #[test]
+    fn test_resolve_testcase_glob_placeholders() {
+        let pattern = PathPattern::compile(&glob_entry("tests/drivers/**/*.rs", "")).unwrap();
+
+        let resolved = resolve_testcase("tests/drivers/api/login.rs", &pattern, "{dir}|{stem}|{ext}|{path}");
+
+        assert_eq!(resolved, Some("tests/drivers/api|login|rs|tests/drivers/api/login.rs".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_testcase_glob_no_match() {
+        let pattern = PathPattern::compile(&glob_entry("tests/drivers/*.rs", "{stem}")).unwrap();
+
+        assert_eq!(resolve_testcase("tests/drivers/api/login.rs", &pattern, "{stem}"), None);
+        assert_eq!(resolve_testcase("src/main.rs", &pattern, "{stem}"), None);
+    }
+
+    #[test]
+    fn test_find_driver_matched_files_with_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        for file in ["tests/drivers/a.rs", "tests/drivers/nested/b.rs", "tests/drivers/c.txt", "src/main.rs"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let patterns = compile_patterns(&[glob_entry("tests/drivers/**/*.rs", "{stem}")]).unwrap();
+
+        let drivers = find_matched_files(&patterns, &[], &[], &[], temp_dir.path(), &mut ScanCache::default()).unwrap().drivers;
+
+        assert_eq!(drivers, vec![
+            ("tests/drivers/a.rs".to_string(), 0),
+            ("tests/drivers/nested/b.rs".to_string(), 0),
+        ]);
+    }
+
+    #[test]
+    fn test_config_load_accepts_glob_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = load(&temp_dir, r#"
+[[driver_patterns]]
+glob = "tests/drivers/**/*.rs"
+testcase = "{stem}"
+"#).unwrap();
+
+        assert_eq!(config.driver_patterns[0].source(), "tests/drivers/**/*.rs");
+    }
+
+    #[test]
+    fn test_config_load_rejects_pattern_and_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = load(&temp_dir, r#"
+[[mock_patterns]]
+pattern = "src/(.+)\\.rs"
+glob = "src/**/*.rs"
+testcase = "$1"
+"#).unwrap_err();
+
+        assert!(format!("{:#}", error).contains("pattern and glob are mutually exclusive in [[mock_patterns]]"));
+    }
+
+    #[test]
+    fn test_config_load_requires_pattern_or_glob() {
+        let temp_dir = TempDir::new().unwrap();
+        let error = load(&temp_dir, r#"
+[[driver_patterns]]
+testcase = "$1"
+"#).unwrap_err();
+
+        assert!(format!("{:#}", error).contains("Either pattern or glob is required in [[driver_patterns]]"));
+    }
+}
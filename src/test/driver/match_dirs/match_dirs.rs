@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{compile_patterns, find_matched_files, match_dirs, ScanCache};
+
+    fn setup(toml_content: &str) -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        for file in ["suites/login/steps.txt", "suites/login/data.json", "suites/checkout/steps.txt", "suites/README.md"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, toml_content).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        (temp_dir, config)
+    }
+
+    fn drivers(temp_dir: &TempDir, config: &Config, cache: &mut ScanCache) -> Vec<(String, usize)> {
+        let patterns = compile_patterns(&config.driver_patterns).unwrap();
+        find_matched_files(&patterns, &match_dirs(&config.driver_patterns), &[], &[], temp_dir.path(), cache)
+            .unwrap()
+            .drivers
+    }
+
+    #[test]
+    fn test_match_dirs_pattern_matches_directories() {
+        let (temp_dir, config) = setup(r#"
+[[driver_patterns]]
+pattern = "^suites/([^/]+)$"
+testcase = "$1"
+match_dirs = true
+"#);
+
+        assert_eq!(drivers(&temp_dir, &config, &mut ScanCache::default()), vec![
+            ("suites/README.md".to_string(), 0),
+            ("suites/checkout".to_string(), 0),
+            ("suites/login".to_string(), 0),
+        ]);
+    }
+
+    #[test]
+    fn test_directories_are_skipped_without_match_dirs() {
+        let (temp_dir, config) = setup(r#"
+[[driver_patterns]]
+pattern = "^suites/([^/]+)$"
+testcase = "$1"
+"#);
+
+        assert_eq!(drivers(&temp_dir, &config, &mut ScanCache::default()), vec![
+            ("suites/README.md".to_string(), 0),
+        ]);
+    }
+
+    #[test]
+    fn test_toggling_match_dirs_invalidates_scan_cache() {
+        let (temp_dir, config) = setup(r#"
+[[driver_patterns]]
+pattern = "^suites/([^/]+)$"
+testcase = "$1"
+"#);
+        let mut cache = ScanCache::default();
+        drivers(&temp_dir, &config, &mut cache);
+
+        let mut with_dirs = config.driver_patterns.clone();
+        with_dirs[0].match_dirs = true;
+        let patterns = compile_patterns(&with_dirs).unwrap();
+        let rescanned = find_matched_files(&patterns, &match_dirs(&with_dirs), &[], &[], temp_dir.path(), &mut cache)
+            .unwrap()
+            .drivers;
+
+        assert_eq!(rescanned.len(), 3);
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::Path;
+    use filetime::{set_file_mtime, FileTime};
+    use regex::Regex;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{apply_mock_mounts, check_mock_conflicts, plan_mock_mounts, resolve_testcase, MockMount, PathPattern};
+
+    fn mock_mount(temp_dir: &TempDir) -> MockMount {
+        let mock_abs_path = temp_dir.path().join("src/foo/mock/bar/baz.rs");
+        fs::create_dir_all(mock_abs_path.parent().unwrap()).unwrap();
+        fs::write(&mock_abs_path, "").unwrap();
+        set_file_mtime(&mock_abs_path, FileTime::from_unix_time(1_000_000, 0)).unwrap();
+        
+        MockMount {
+            mock_path: "src/foo/mock/bar/baz.rs".to_string(),
+            original_path: "src/foo.rs".to_string(),
+            mock_abs_path,
+            original_abs_path: temp_dir.path().join("src/foo.rs"),
+        }
+    }
+
+    fn mtime_of(mount: &MockMount) -> FileTime {
+        FileTime::from_last_modification_time(&fs::metadata(&mount.mock_abs_path).unwrap())
+    }
+
+    #[test]
+    fn test_touch_mocks_defaults_to_true() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+"#).unwrap();
+        
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.unwrap().test.unwrap();
+        
+        assert!(run_test.touch_mocks);
+    }
+
+    #[test]
+    fn test_apply_mock_mounts_refreshes_mtime_when_touch_mocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let mount = mock_mount(&temp_dir);
+        let mut mount_args = Vec::new();
+        
+        let backups = apply_mock_mounts(std::slice::from_ref(&mount), true, &mut mount_args).unwrap();
+        
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].1, FileTime::from_unix_time(1_000_000, 0));
+        assert_ne!(mtime_of(&mount), FileTime::from_unix_time(1_000_000, 0));
+        assert_eq!(mount_args.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_mock_mounts_leaves_mtime_untouched_when_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+command = "cargo"
+args = ["test"]
+touch_mocks = false
+"#).unwrap();
+        let run_test = Config::load(&config_path).unwrap().command.unwrap().test.unwrap();
+        let mount = mock_mount(&temp_dir);
+        let mut mount_args = Vec::new();
+        
+        let backups = apply_mock_mounts(std::slice::from_ref(&mount), run_test.touch_mocks, &mut mount_args).unwrap();
+        
+        assert!(backups.is_empty());
+        assert_eq!(mtime_of(&mount), FileTime::from_unix_time(1_000_000, 0));
+        assert_eq!(mount_args[0], "-v");
+        assert_eq!(
+            mount_args[1],
+            format!("{}:{}:ro", mount.mock_abs_path.display(), mount.original_abs_path.display())
+        );
+    }
+
+    fn conflicting_mounts(temp_dir: &TempDir) -> Vec<MockMount> {
+        let first = mock_mount(temp_dir);
+        let mut second = first.clone();
+        second.mock_path = "src/foo/mock/other/baz.rs".to_string();
+        second.mock_abs_path = temp_dir.path().join(&second.mock_path);
+        vec![first, second]
+    }
+
+    #[test]
+    fn test_check_mock_conflicts_names_both_mocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let mounts = conflicting_mounts(&temp_dir);
+        
+        let error_msg = check_mock_conflicts(&mounts, false).unwrap_err().to_string();
+        
+        assert!(error_msg.contains("src/foo/mock/bar/baz.rs"));
+        assert!(error_msg.contains("src/foo/mock/other/baz.rs"));
+        assert!(error_msg.contains(&temp_dir.path().join("src/foo.rs").display().to_string()));
+    }
+
+    #[test]
+    fn test_check_mock_conflicts_allowed_only_warns() {
+        let temp_dir = TempDir::new().unwrap();
+        let mounts = conflicting_mounts(&temp_dir);
+        
+        assert!(check_mock_conflicts(&mounts, true).is_ok());
+    }
+
+    #[test]
+    fn test_check_mock_conflicts_distinct_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut mounts = conflicting_mounts(&temp_dir);
+        mounts[1].original_abs_path = temp_dir.path().join("src/bar.rs");
+        
+        assert!(check_mock_conflicts(&mounts, false).is_ok());
+    }
+
+    fn plan(mock_files: &[&str], allow_conflicts: bool) -> anyhow::Result<Vec<MockMount>> {
+        let testcase = "$2_$3".to_string();
+        let compiled = [
+            PathPattern::Regex(Regex::new("src/([^/]+)/mock/([^/]+)/([^/]+)\\.rs").unwrap()),
+            PathPattern::Regex(Regex::new("src/([^/]+)/mock_v2/([^/]+)/([^/]+)\\.rs").unwrap()),
+        ];
+        let patterns: Vec<_> = compiled.iter().map(|pattern| (pattern, &testcase, Some("src/$1.rs"))).collect();
+        let mut mock_map: HashMap<String, Vec<String>> = HashMap::new();
+        let mut mock_file_info = Vec::new();
+        for mock_file in mock_files {
+            for (pattern, testcase, mount_path) in &patterns {
+                if let Some(key) = resolve_testcase(mock_file, pattern, testcase) {
+                    mock_map.entry(key.clone()).or_default().push(mock_file.to_string());
+                    mock_file_info.push((mock_file.to_string(), key, *mount_path));
+                    break;
+                }
+            }
+        }
+        
+        plan_mock_mounts("bar_baz", &mock_map, &mock_file_info, &patterns, Path::new("/repo"), allow_conflicts)
+    }
+
+    #[test]
+    fn test_plan_mock_mounts_sorted_by_mock_path() {
+        let mounts = plan(&["src/qux/mock/bar/baz.rs", "src/foo/mock/bar/baz.rs"], false).unwrap();
+        
+        let planned: Vec<(&str, &str)> = mounts
+            .iter()
+            .map(|mount| (mount.mock_path.as_str(), mount.original_path.as_str()))
+            .collect();
+        assert_eq!(planned, vec![
+            ("src/foo/mock/bar/baz.rs", "src/foo.rs"),
+            ("src/qux/mock/bar/baz.rs", "src/qux.rs"),
+        ]);
+    }
+
+    #[test]
+    fn test_plan_mock_mounts_rejects_two_patterns_claiming_same_target() {
+        let result = plan(&["src/foo/mock_v2/bar/baz.rs", "src/foo/mock/bar/baz.rs"], false);
+        
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("src/foo/mock/bar/baz.rs, src/foo/mock_v2/bar/baz.rs"));
+        assert!(error_msg.contains("/repo/src/foo.rs"));
+    }
+
+    #[test]
+    fn test_plan_mock_mounts_allows_conflicts_when_configured() {
+        let mounts = plan(&["src/foo/mock_v2/bar/baz.rs", "src/foo/mock/bar/baz.rs"], true).unwrap();
+        
+        assert_eq!(mounts.len(), 2);
+        assert_eq!(mounts[0].mock_path, "src/foo/mock/bar/baz.rs");
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+    use crate::test::{resolve_driver_key, resolve_testcase, PathPattern};
+
+    fn pattern(regex: &str) -> PathPattern {
+        PathPattern::Regex(Regex::new(regex).unwrap())
+    }
+
+    #[test]
+    fn test_resolve_testcase_named_groups() {
+        let pattern = pattern("src/(?P<mod>[^/]+)/mock/(?P<case>[^/]+)\\.rs");
+
+        assert_eq!(
+            resolve_testcase("src/parser/mock/tokens.rs", &pattern, "${mod}/test.rs"),
+            Some("parser/test.rs".to_string())
+        );
+        assert_eq!(
+            resolve_testcase("src/parser/mock/tokens.rs", &pattern, "${mod}_$2"),
+            Some("parser_tokens".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_named_group_is_unresolved() {
+        let pattern = pattern("src/(?P<mod>[^/]+)/driver/[^/]+\\.rs");
+        let testcase = "${module}".to_string();
+
+        let error_msg = resolve_driver_key("src/parser/driver/lex.rs", &[(&pattern, &testcase)], None, true)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error_msg.contains("No testcase key could be resolved for src/parser/driver/lex.rs"));
+    }
+}
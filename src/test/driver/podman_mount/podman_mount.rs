@@ -1,15 +1,16 @@
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use tempfile::TempDir;
-    use crate::podman_mount::build_mount_args;
+    use crate::config::VolumeMount;
+    use crate::podman_mount::{build_mount_args, build_volume_mount_args};
 
     #[test]
     fn test_build_mount_args_with_simple_path() {
         let temp_dir = TempDir::new().unwrap();
         let root_dir = temp_dir.path();
         
-        let args = build_mount_args(root_dir);
+        let args = build_mount_args(root_dir).unwrap();
         
         assert_eq!(args.len(), 2);
         assert_eq!(args[0], "-v");
@@ -23,7 +24,7 @@ mod tests {
         let path_with_spaces = temp_dir.path().join("path with spaces");
         std::fs::create_dir_all(&path_with_spaces).unwrap();
         
-        let args = build_mount_args(&path_with_spaces);
+        let args = build_mount_args(&path_with_spaces).unwrap();
         
         assert_eq!(args.len(), 2);
         assert_eq!(args[0], "-v");
@@ -35,7 +36,7 @@ mod tests {
     fn test_build_mount_args_with_absolute_path() {
         let root_dir = PathBuf::from("/tmp/test");
         
-        let args = build_mount_args(&root_dir);
+        let args = build_mount_args(&root_dir).unwrap();
         
         assert_eq!(args.len(), 2);
         assert_eq!(args[0], "-v");
@@ -48,7 +49,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let root_dir = temp_dir.path();
         
-        let args = build_mount_args(root_dir);
+        let args = build_mount_args(root_dir).unwrap();
         
         assert_eq!(args.len(), 2);
         assert_eq!(args[0], "-v");
@@ -58,5 +59,42 @@ mod tests {
         assert_eq!(parts[0], parts[1]); 
         assert_eq!(parts[0], root_dir.display().to_string());
     }
-}
 
+    #[test]
+    fn test_build_mount_args_with_relative_path() {
+        let args = build_mount_args(Path::new("src")).unwrap();
+        
+        let root_dir = Path::new("src").canonicalize().unwrap();
+        assert!(root_dir.is_absolute());
+        assert_eq!(args[1], format!("{}:{}", root_dir.display(), root_dir.display()));
+    }
+
+    #[test]
+    fn test_build_mount_args_with_missing_relative_path() {
+        let result = build_mount_args(Path::new("missing-root-dir"));
+        
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("absolute path"));
+    }
+
+    #[test]
+    fn test_build_mount_args_rejects_filesystem_root() {
+        let result = build_mount_args(&PathBuf::from("/"));
+        
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("filesystem root"));
+    }
+
+    #[test]
+    fn test_build_volume_mount_args() {
+        let volumes = vec![
+            VolumeMount { name: "pgdata".to_string(), container_path: "/var/lib/postgresql/data".to_string() },
+            VolumeMount { name: "cache".to_string(), container_path: "/cache".to_string() },
+        ];
+        
+        let args = build_volume_mount_args(&volumes);
+        
+        assert_eq!(args, vec!["-v", "pgdata:/var/lib/postgresql/data", "-v", "cache:/cache"]);
+    }
+}
@@ -0,0 +1,302 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::{Config, ContainerMode};
+    use crate::test::{
+        build_command_check_args, build_exec_kill_args, build_test_exec_args, build_test_podman_args, compile_patterns, load_driver_env,
+        TestInvocation,
+    };
+
+    fn env_file_config(temp_dir: &TempDir) -> Config {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "(.+)/driver/([^/]+)\\.(rs)"
+testcase = "$2"
+env_file_pattern = "$1/$2.env.$3"
+
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test", "{testcase}"]
+"#).unwrap();
+        Config::load(&config_path).unwrap()
+    }
+
+    #[test]
+    fn test_build_command_check_args_uses_image_and_command() {
+        let args = build_command_check_args("docker.io/library/rust:latest", "cargo");
+        
+        assert_eq!(
+            args,
+            vec!["run", "--rm", "docker.io/library/rust:latest", "which", "cargo"]
+        );
+    }
+
+    #[test]
+    fn test_driver_env_file_passed_as_env_flags() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = env_file_config(&temp_dir);
+        fs::create_dir_all(temp_dir.path().join("src/foo/driver")).unwrap();
+        fs::write(temp_dir.path().join("src/foo/seeded.env.rs"), "# fixtures\nSEED=42\n\nFEATURE_X=on\n").unwrap();
+
+        let env_vars = load_driver_env(&config.driver_patterns, &compile_patterns(&config.driver_patterns).unwrap(), "src/foo/driver/seeded.rs", 0, temp_dir.path()).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        let mount_args = vec!["-v".to_string(), "/repo:/repo".to_string()];
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/seeded.rs",
+            testcase: Some("seeded"),
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &mount_args,
+            env_vars: &env_vars,
+        };
+        let args = build_test_podman_args(run_test, &invocation, None).unwrap();
+
+        let root_dir = temp_dir.path().display().to_string();
+        let root_dir_env = format!("OVERCODE_ROOT_DIR={}", root_dir);
+        assert_eq!(
+            args,
+            vec![
+                "run", "--rm",
+                "-v", "/repo:/repo",
+                "-e", "OVERCODE_DRIVER_FILE=src/foo/driver/seeded.rs",
+                "-e", "OVERCODE_TESTCASE=seeded",
+                "-e", root_dir_env.as_str(),
+                "-e", "OVERCODE_RUN_ID=1700000000",
+                "-e", "SEED=42",
+                "-e", "FEATURE_X=on",
+                "-w", root_dir.as_str(),
+                "docker.io/library/rust:latest",
+                "cargo", "test", "seeded",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_driver_without_env_file_gets_no_env_flags() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = env_file_config(&temp_dir);
+
+        let env_vars = load_driver_env(&config.driver_patterns, &compile_patterns(&config.driver_patterns).unwrap(), "src/foo/driver/plain.rs", 0, temp_dir.path()).unwrap();
+
+        assert!(env_vars.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_driver_env_file_fails_with_line_number() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = env_file_config(&temp_dir);
+        fs::create_dir_all(temp_dir.path().join("src/foo")).unwrap();
+        fs::write(temp_dir.path().join("src/foo/broken.env.rs"), "SEED=42\nFEATURE_X\n").unwrap();
+
+        let error = load_driver_env(&config.driver_patterns, &compile_patterns(&config.driver_patterns).unwrap(), "src/foo/driver/broken.rs", 0, temp_dir.path()).unwrap_err();
+
+        assert!(format!("{:#}", error).contains("line 2"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_inject_env_disabled_omits_context_variables() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test"]
+inject_env = false
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/plain.rs",
+            testcase: None,
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &[],
+        };
+        let args = build_test_podman_args(run_test, &invocation, None).unwrap();
+
+        assert!(!args.iter().any(|arg| arg.starts_with("OVERCODE_")));
+        assert!(!args.contains(&"-e".to_string()));
+    }
+
+    #[test]
+    fn test_driver_file_abs_placeholder_joins_root_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/python:3"
+command = "python"
+args = ["{driver_file_abs}", "{driver_file}"]
+inject_env = false
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/bar/baz.py",
+            testcase: None,
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &[],
+        };
+        let args = build_test_podman_args(run_test, &invocation, None).unwrap();
+
+        let expected_abs = temp_dir.path().join("src/foo/driver/bar/baz.py").display().to_string();
+        assert_eq!(args[args.len() - 2], expected_abs);
+        assert_eq!(args[args.len() - 1], "src/foo/driver/bar/baz.py");
+    }
+
+    #[test]
+    fn test_shared_container_mode_runs_driver_via_exec() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test", "{testcase}"]
+container_mode = "shared"
+inject_env = false
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        assert_eq!(run_test.container_mode, ContainerMode::Shared);
+
+        let env_vars = vec![("SEED".to_string(), "42".to_string())];
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/bar/baz.rs",
+            testcase: Some("bar_baz"),
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &env_vars,
+        };
+        let args = build_test_exec_args(run_test, &invocation, "abc123", None).unwrap();
+
+        let root_dir = temp_dir.path().display().to_string();
+        assert_eq!(
+            args,
+            vec![
+                "exec",
+                "-e", "SEED=42",
+                "-w", root_dir.as_str(),
+                "abc123",
+                "cargo", "test", "bar_baz",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shared_container_deadline_records_pid_and_kills_inside_container() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test"]
+container_mode = "shared"
+inject_env = false
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/bar/baz.rs",
+            testcase: None,
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &[],
+        };
+
+        let args = build_test_exec_args(run_test, &invocation, "abc123", Some("/tmp/overcode-test-1-0.pid")).unwrap();
+
+        let container = args.iter().position(|arg| arg == "abc123").unwrap();
+        assert_eq!(
+            args[container + 1..],
+            ["sh", "-c", "echo $$ > /tmp/overcode-test-1-0.pid && exec \"$@\"", "sh", "cargo", "test"]
+        );
+        assert_eq!(
+            build_exec_kill_args("abc123", "/tmp/overcode-test-1-0.pid"),
+            vec![
+                "exec", "abc123", "sh", "-c",
+                "kill -KILL $(cat /tmp/overcode-test-1-0.pid) 2>/dev/null; rm -f /tmp/overcode-test-1-0.pid",
+            ]
+        );
+    }
+
+    fn container_env_file_config(temp_dir: &TempDir) -> Config {
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[command.test]
+image = "docker.io/library/rust:latest"
+command = "cargo"
+args = ["test"]
+inject_env = false
+env_file = "config/test.env"
+"#).unwrap();
+        Config::load(&config_path).unwrap()
+    }
+
+    #[test]
+    fn test_container_env_file_passed_as_env_file_flag() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = container_env_file_config(&temp_dir);
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+        fs::create_dir_all(temp_dir.path().join("config")).unwrap();
+        fs::write(temp_dir.path().join("config/test.env"), "DATABASE_URL=postgres://db\n").unwrap();
+
+        let env_vars = vec![("SEED".to_string(), "42".to_string())];
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/plain.rs",
+            testcase: None,
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &env_vars,
+        };
+        let args = build_test_podman_args(run_test, &invocation, None).unwrap();
+
+        let env_file = temp_dir.path().join("config/test.env").display().to_string();
+        assert_eq!(
+            &args[2..6],
+            &["--env-file", env_file.as_str(), "-e", "SEED=42"]
+        );
+    }
+
+    #[test]
+    fn test_missing_container_env_file_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = container_env_file_config(&temp_dir);
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+
+        let invocation = TestInvocation {
+            driver_file: "src/foo/driver/plain.rs",
+            testcase: None,
+            root_dir: temp_dir.path(),
+            run_id: "1700000000",
+            mount_args: &[],
+            env_vars: &[],
+        };
+        let error_msg = build_test_exec_args(run_test, &invocation, "abc123", None).unwrap_err().to_string();
+
+        assert!(error_msg.contains("env_file not found"));
+        assert!(error_msg.contains("config/test.env"));
+    }
+
+    #[test]
+    fn test_container_mode_defaults_to_per_test() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = env_file_config(&temp_dir);
+        let run_test = config.command.as_ref().unwrap().test.as_ref().unwrap();
+
+        assert_eq!(run_test.container_mode, ContainerMode::PerTest);
+    }
+}
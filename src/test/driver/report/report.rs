@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process::Command;
+    use std::time::Duration;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{
+        captured_output, process_test, process_test_report, CommandOutcome, DriverDisposition, DriverResult,
+        DriverStatus, TestOptions, TestReport, TestsFailed,
+    };
+
+    fn result(driver_file: &str, status: DriverStatus, disposition: DriverDisposition) -> DriverResult {
+        DriverResult {
+            driver_file: driver_file.to_string(),
+            testcase: None,
+            status,
+            disposition,
+            duration: Duration::from_millis(10),
+            failure_excerpt: None,
+            log_path: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    fn setup() -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "tests/(.+)\\.sh"
+testcase = "$1"
+
+[command.test]
+command = "sh"
+args = ["{driver_file}"]
+"#).unwrap();
+        fs::create_dir_all(temp_dir.path().join("tests")).unwrap();
+        fs::write(temp_dir.path().join("tests/broken.sh"), "exit 1\n").unwrap();
+        let config = Config::load(&config_path).unwrap();
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn test_report_counts_passed_and_failed_drivers() {
+        let report = TestReport::new(vec![
+            result("src/a/driver/ok.rs", DriverStatus::Passed, DriverDisposition::Ran),
+            result("src/a/driver/broken.rs", DriverStatus::Failed, DriverDisposition::Ran),
+            result("src/a/driver/misconfigured.rs", DriverStatus::ConfigError, DriverDisposition::FailedPlanning),
+            result("src/a/driver/late.rs", DriverStatus::NotRun, DriverDisposition::SkippedDeadline),
+        ]);
+
+        assert_eq!(report.results.len(), 4);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.failed, 2);
+    }
+
+    #[test]
+    fn test_failed_driver_is_reported_instead_of_returned_as_error() {
+        let (temp_dir, config) = setup();
+
+        let report = process_test_report(&config, temp_dir.path(), &TestOptions::default()).unwrap();
+
+        assert_eq!(report.passed, 0);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.results[0].driver_file, "tests/broken.sh");
+        assert_eq!(report.results[0].testcase.as_deref(), Some("broken"));
+        assert_eq!(report.results[0].status, DriverStatus::Failed);
+    }
+
+    #[test]
+    fn test_process_test_still_fails_on_failed_driver() {
+        let (temp_dir, config) = setup();
+
+        let err = process_test(&config, temp_dir.path(), &TestOptions::default()).unwrap_err();
+
+        assert!(err.downcast_ref::<TestsFailed>().is_some());
+    }
+
+    #[test]
+    fn test_output_is_captured_for_passing_and_failing_commands() {
+        for script in ["echo out; echo err >&2", "echo out; echo err >&2; exit 3"] {
+            let output = Command::new("sh").args(["-c", script]).output().unwrap();
+
+            let (stdout, stderr) = captured_output(&Ok(CommandOutcome::Finished(output)));
+
+            assert_eq!(stdout.as_deref(), Some("out\n"));
+            assert_eq!(stderr.as_deref(), Some("err\n"));
+        }
+    }
+
+    #[test]
+    fn test_output_is_not_captured_when_command_did_not_finish() {
+        assert_eq!(captured_output(&Ok(CommandOutcome::DeadlineExceeded)), (None, None));
+        assert_eq!(captured_output(&Err(anyhow::anyhow!("spawn failed"))), (None, None));
+    }
+
+    #[test]
+    fn test_result_json_includes_captured_output() {
+        let mut passed = result("src/a/driver/ok.rs", DriverStatus::Passed, DriverDisposition::Ran);
+        passed.stdout = Some("ok\n".to_string());
+        passed.stderr = Some(String::new());
+
+        let json = passed.to_json();
+
+        assert_eq!(json["stdout"], "ok\n");
+        assert_eq!(json["stderr"], "");
+        assert_eq!(passed.status.label(), "PASS");
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use regex::Regex;
+    use crate::test::{
+        check_driver_results, format_summary_table, resolve_driver_key, DriverDisposition, DriverResult, DriverStatus,
+        PathPattern, TestOptions,
+    };
+
+    fn compiled(entries: &[(&str, &'static String)]) -> Vec<(&'static PathPattern, &'static String)> {
+        entries
+            .iter()
+            .map(|(pattern, testcase)| {
+                let pattern: &'static PathPattern = Box::leak(Box::new(PathPattern::Regex(Regex::new(pattern).unwrap())));
+                (pattern, *testcase)
+            })
+            .collect()
+    }
+
+    fn template(value: &str) -> &'static String {
+        Box::leak(Box::new(value.to_string()))
+    }
+
+    #[test]
+    fn test_resolve_driver_key_with_captures() {
+        let patterns = compiled(&[("src/([^/]+)/driver/([^/]+)/([^/]+)\\.rs", template("$2_$3"))]);
+
+        let key = resolve_driver_key("src/foo/driver/bar/baz.rs", &patterns, None, true).unwrap();
+
+        assert_eq!(key, Some("bar_baz".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_driver_key_permissive_keeps_running() {
+        let patterns = compiled(&[("src/[^/]+/driver/.+\\.rs", template("$1"))]);
+
+        let key = resolve_driver_key("src/foo/driver/bar/baz.rs", &patterns, None, false).unwrap();
+
+        assert_eq!(key, Some("$1".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_driver_key_strict_reports_patterns_tried() {
+        let patterns = compiled(&[
+            ("src/[^/]+/driver/.+\\.rs", template("$1")),
+            ("tests/(.+)\\.rs", template("$1")),
+        ]);
+
+        let error_msg = resolve_driver_key("src/foo/driver/bar/baz.rs", &patterns, None, true)
+            .unwrap_err()
+            .to_string();
+
+        assert!(error_msg.contains("src/foo/driver/bar/baz.rs"));
+        assert!(error_msg.contains("src/[^/]+/driver/.+\\.rs -> $1"));
+        assert!(error_msg.contains("tests/(.+)\\.rs -> $1"));
+    }
+
+    #[test]
+    fn test_config_errors_listed_in_summary_and_fail_run() {
+        let results = vec![
+            DriverResult {
+                driver_file: "src/a/driver/x/ok.rs".to_string(),
+                testcase: Some("x_ok".to_string()),
+                status: DriverStatus::Passed,
+                disposition: DriverDisposition::Ran,
+                duration: Duration::from_millis(500),
+                failure_excerpt: None,
+                log_path: None,
+                stdout: None,
+                stderr: None,
+            },
+            DriverResult {
+                driver_file: "src/b/driver/misconfigured.rs".to_string(),
+                testcase: None,
+                status: DriverStatus::ConfigError,
+                disposition: DriverDisposition::FailedPlanning,
+                duration: Duration::ZERO,
+                failure_excerpt: None,
+                log_path: None,
+                stdout: None,
+                stderr: Some("No testcase key could be resolved".to_string()),
+            },
+        ];
+
+        let table = format_summary_table(&results);
+        assert!(table.contains("CONFIG         -  src/b/driver/misconfigured.rs"));
+        assert!(table.contains("Configuration errors:\n  src/b/driver/misconfigured.rs: No testcase key could be resolved\n"));
+
+        let error_msg = check_driver_results(&results, &TestOptions::default()).unwrap_err().to_string();
+        assert!(error_msg.contains("could not resolve a testcase key: src/b/driver/misconfigured.rs"));
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use filetime::{set_file_mtime, FileTime};
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{compile_patterns, compile_test_ignores, find_matched_files, MatchedFiles, ScanCache};
+
+    fn setup() -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        let driver_dir = temp_dir.path().join("src/foo/driver/bar");
+        fs::create_dir_all(&driver_dir).unwrap();
+        fs::write(driver_dir.join("one.rs"), "").unwrap();
+        
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "src/([^/]+)/driver/([^/]+)/([^/]+)\\.rs"
+testcase = "$2_$3"
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+        
+        (temp_dir, config)
+    }
+
+    fn scan(config: &Config, root_dir: &Path, cache: &mut ScanCache) -> Vec<(String, usize)> {
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+        let ignore_patterns = compile_test_ignores(&config.test_ignores).unwrap();
+        find_matched_files(&driver_patterns, &[], &[], &ignore_patterns, root_dir, cache).unwrap().drivers
+    }
+
+    #[test]
+    fn test_scan_cache_reused_until_directory_mtime_changes() {
+        let (temp_dir, config) = setup();
+        let mut cache = ScanCache::default();
+        
+        let first = scan(&config, temp_dir.path(), &mut cache);
+        assert_eq!(first, vec![("src/foo/driver/bar/one.rs".to_string(), 0)]);
+        let first_scanned_at = cache.scanned_at;
+        assert!(first_scanned_at.is_some());
+        
+        let second = scan(&config, temp_dir.path(), &mut cache);
+        assert_eq!(second, first);
+        assert_eq!(cache.scanned_at, first_scanned_at);
+        
+        let driver_dir = temp_dir.path().join("src/foo/driver/bar");
+        fs::write(driver_dir.join("two.rs"), "").unwrap();
+        set_file_mtime(&driver_dir, FileTime::from_unix_time(1_000_000, 0)).unwrap();
+        
+        let third = scan(&config, temp_dir.path(), &mut cache);
+        assert_eq!(third, vec![
+            ("src/foo/driver/bar/one.rs".to_string(), 0),
+            ("src/foo/driver/bar/two.rs".to_string(), 0),
+        ]);
+        assert_ne!(cache.scanned_at, first_scanned_at);
+    }
+
+    #[test]
+    fn test_scan_cache_invalidate_forces_rescan() {
+        let (temp_dir, config) = setup();
+        let mut cache = ScanCache::default();
+        
+        scan(&config, temp_dir.path(), &mut cache);
+        assert!(cache.scanned_at.is_some());
+        
+        cache.invalidate();
+        assert!(cache.scanned_at.is_none());
+        assert!(cache.files.drivers.is_empty());
+        
+        let files = scan(&config, temp_dir.path(), &mut cache);
+        assert_eq!(files, vec![("src/foo/driver/bar/one.rs".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_scan_cache_not_shared_across_root_dirs() {
+        let (first_dir, config) = setup();
+        let second_dir = TempDir::new().unwrap();
+        let mut cache = ScanCache::default();
+        
+        scan(&config, first_dir.path(), &mut cache);
+        let files = scan(&config, second_dir.path(), &mut cache);
+        
+        assert!(files.is_empty());
+    }
+
+    fn fixture() -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        for file in [
+            "src/foo/driver/bar/one.rs",
+            "src/foo/driver/bar/two.rs",
+            "src/foo/mock/bar/one.rs",
+            "src/foo/shared/both.rs",
+            "README.md",
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+[[driver_patterns]]
+pattern = "src/([^/]+)/driver/([^/]+)/([^/]+)\\.rs"
+testcase = "$2_$3"
+
+[[driver_patterns]]
+pattern = "src/([^/]+)/shared/([^/]+)\\.rs"
+testcase = "$2"
+
+[[mock_patterns]]
+pattern = "src/([^/]+)/mock/([^/]+)/([^/]+)\\.rs"
+testcase = "$2_$3"
+
+[[mock_patterns]]
+glob = "src/*/shared/*.rs"
+testcase = "{stem}"
+"#).unwrap();
+        let config = Config::load(&config_path).unwrap();
+
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn test_single_walk_matches_separate_scans() {
+        let (temp_dir, config) = fixture();
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+        let mock_patterns = compile_patterns(&config.mock_patterns).unwrap();
+
+        let combined = find_matched_files(&driver_patterns, &[], &mock_patterns, &[], temp_dir.path(), &mut ScanCache::default()).unwrap();
+        let drivers_only = find_matched_files(&driver_patterns, &[], &[], &[], temp_dir.path(), &mut ScanCache::default()).unwrap();
+        let mocks_only = find_matched_files(&[], &[], &mock_patterns, &[], temp_dir.path(), &mut ScanCache::default()).unwrap();
+
+        assert_eq!(combined, MatchedFiles { drivers: drivers_only.drivers, mocks: mocks_only.mocks });
+        assert_eq!(combined.drivers, vec![
+            ("src/foo/driver/bar/one.rs".to_string(), 0),
+            ("src/foo/driver/bar/two.rs".to_string(), 0),
+            ("src/foo/shared/both.rs".to_string(), 1),
+        ]);
+        assert_eq!(combined.mocks, vec!["src/foo/mock/bar/one.rs", "src/foo/shared/both.rs"]);
+    }
+
+    #[test]
+    fn test_scan_cache_invalidated_by_mock_pattern_change() {
+        let (temp_dir, config) = fixture();
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+        let mock_patterns = compile_patterns(&config.mock_patterns).unwrap();
+        let mut cache = ScanCache::default();
+
+        let first = find_matched_files(&driver_patterns, &[], &mock_patterns[..1], &[], temp_dir.path(), &mut cache).unwrap();
+        let second = find_matched_files(&driver_patterns, &[], &mock_patterns, &[], temp_dir.path(), &mut cache).unwrap();
+
+        assert_eq!(first.mocks, vec!["src/foo/mock/bar/one.rs"]);
+        assert_eq!(second.mocks, vec!["src/foo/mock/bar/one.rs", "src/foo/shared/both.rs"]);
+    }
+}
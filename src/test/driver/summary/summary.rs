@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::test::{format_summary_table, DriverDisposition, DriverResult, DriverStatus};
+
+    fn result(driver_file: &str, status: DriverStatus, millis: u64) -> DriverResult {
+        DriverResult {
+            driver_file: driver_file.to_string(),
+            testcase: None,
+            status,
+            disposition: match status {
+                DriverStatus::NotRun => DriverDisposition::SkippedDeadline,
+                DriverStatus::Excluded => DriverDisposition::SkippedExcluded,
+                DriverStatus::ConfigError => DriverDisposition::FailedPlanning,
+                DriverStatus::Passed | DriverStatus::Failed => DriverDisposition::Ran,
+            },
+            duration: Duration::from_millis(millis),
+            failure_excerpt: None,
+            log_path: None,
+            stdout: None,
+            stderr: None,
+        }
+    }
+
+    #[test]
+    fn test_format_summary_table_sorted_by_duration_descending() {
+        let results = vec![
+            result("src/a/driver/x/fast.rs", DriverStatus::Passed, 500),
+            result("src/b/driver/x/slow.rs", DriverStatus::Failed, 12_340),
+            result("src/c/driver/x/mid.rs", DriverStatus::Passed, 2_000),
+        ];
+        
+        let table = format_summary_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "STATUS  DURATION  DRIVER");
+        assert_eq!(lines[1], "FAIL      12.34s  src/b/driver/x/slow.rs");
+        assert_eq!(lines[2], "PASS       2.00s  src/c/driver/x/mid.rs");
+        assert_eq!(lines[3], "PASS       0.50s  src/a/driver/x/fast.rs");
+    }
+
+    #[test]
+    fn test_format_summary_table_without_slowest_callout_for_few_drivers() {
+        let results: Vec<DriverResult> = (0..10)
+            .map(|i| result(&format!("src/m/driver/x/{}.rs", i), DriverStatus::Passed, i * 100))
+            .collect();
+        
+        let table = format_summary_table(&results);
+        
+        assert!(!table.contains("Slowest"));
+    }
+
+    #[test]
+    fn test_format_summary_table_slowest_callout_for_many_drivers() {
+        let results: Vec<DriverResult> = (0..11)
+            .map(|i| result(&format!("src/m/driver/x/{}.rs", i), DriverStatus::Passed, i * 100))
+            .collect();
+        
+        let table = format_summary_table(&results);
+        let callout: Vec<&str> = table
+            .lines()
+            .skip_while(|line| !line.starts_with("Slowest"))
+            .collect();
+        
+        assert_eq!(callout.len(), 6);
+        assert_eq!(callout[0], "Slowest 5 drivers:");
+        assert!(callout[1].ends_with("src/m/driver/x/10.rs"));
+        assert!(callout[5].ends_with("src/m/driver/x/6.rs"));
+    }
+
+    #[test]
+    fn test_format_summary_table_empty_results() {
+        let table = format_summary_table(&[]);
+        
+        assert_eq!(table, "STATUS  DURATION  DRIVER\n");
+    }
+
+    #[test]
+    fn test_format_summary_table_marks_unrun_drivers() {
+        let results = vec![
+            result("src/a/driver/x/killed.rs", DriverStatus::Failed, 1_500),
+            result("src/b/driver/x/later.rs", DriverStatus::NotRun, 0),
+        ];
+        
+        let table = format_summary_table(&results);
+        let lines: Vec<&str> = table.lines().collect();
+        
+        assert_eq!(lines[1], "FAIL       1.50s  src/a/driver/x/killed.rs");
+        assert_eq!(lines[2], "SKIP           -  src/b/driver/x/later.rs");
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::test::{format_tap, DriverDisposition, DriverResult, DriverStatus, OutputFormat};
+
+    fn result(driver_file: &str, testcase: Option<&str>, status: DriverStatus, stderr: Option<&str>) -> DriverResult {
+        DriverResult {
+            driver_file: driver_file.to_string(),
+            testcase: testcase.map(str::to_string),
+            status,
+            disposition: match status {
+                DriverStatus::NotRun => DriverDisposition::SkippedDeadline,
+                DriverStatus::Excluded => DriverDisposition::SkippedExcluded,
+                DriverStatus::ConfigError => DriverDisposition::FailedPlanning,
+                DriverStatus::Passed | DriverStatus::Failed => DriverDisposition::Ran,
+            },
+            duration: Duration::from_millis(1_250),
+            failure_excerpt: None,
+            log_path: None,
+            stdout: None,
+            stderr: stderr.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_format_tap_plan_and_status_lines() {
+        let results = vec![
+            result("src/a/driver/x/one.rs", Some("x_one"), DriverStatus::Passed, None),
+            result("src/a/driver/x/two.rs", Some("x_two"), DriverStatus::Failed, Some("boom\nat line 3")),
+            result("src/a/driver/x/three.rs", None, DriverStatus::NotRun, None),
+        ];
+
+        let tap = format_tap(&results, "");
+        let lines: Vec<&str> = tap.lines().collect();
+
+        assert_eq!(lines[0], "TAP version 13");
+        let plan: usize = lines[1].strip_prefix("1..").unwrap().parse().unwrap();
+        assert_eq!(plan, 3);
+
+        let status_lines: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|line| line.starts_with("ok ") || line.starts_with("not ok "))
+            .collect();
+        assert_eq!(
+            status_lines,
+            vec![
+                "ok 1 - x_one",
+                "not ok 2 - x_two",
+                "ok 3 - src/a/driver/x/three.rs # SKIP deadline exceeded",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_tap_includes_config_snapshot_as_comments() {
+        let results = vec![result("src/a/driver/x/one.rs", Some("x_one"), DriverStatus::Passed, None)];
+
+        let tap = format_tap(&results, "image: rust:latest\nenv: API_TOKEN=***\n");
+
+        assert!(tap.starts_with("TAP version 13\n1..1\n# image: rust:latest\n# env: API_TOKEN=***\nok 1 - x_one\n"));
+    }
+
+    #[test]
+    fn test_format_tap_failure_has_yaml_diagnostics() {
+        let results = vec![
+            result("src/a/driver/x/two.rs", Some("x_two"), DriverStatus::Failed, Some("boom\nat line 3")),
+        ];
+
+        let tap = format_tap(&results, "");
+
+        assert!(tap.ends_with(
+            "not ok 1 - x_two\n  ---\n  driver_file: src/a/driver/x/two.rs\n  duration_ms: 1250\n  stderr: |\n    boom\n    at line 3\n  ...\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_tap_without_drivers_has_empty_plan() {
+        assert_eq!(format_tap(&[], ""), "TAP version 13\n1..0\n");
+    }
+
+    #[test]
+    fn test_format_tap_with_only_skipped_drivers() {
+        let mut filtered = result("src/a/driver/x/one.rs", Some("x_one"), DriverStatus::NotRun, None);
+        filtered.disposition = DriverDisposition::SkippedFilter;
+        let excluded = result("src/a/driver/x/two.rs", Some("x_two"), DriverStatus::Excluded, None);
+
+        let tap = format_tap(&[filtered, excluded], "");
+
+        assert_eq!(
+            tap,
+            "TAP version 13\n1..2\nok 1 - x_one # SKIP driver filter\nok 2 - x_two # SKIP excluded (config)\n"
+        );
+    }
+
+    #[test]
+    fn test_output_format_from_name() {
+        assert_eq!(OutputFormat::from_name("tap").unwrap(), OutputFormat::Tap);
+        assert_eq!(OutputFormat::from_name("human").unwrap(), OutputFormat::Human);
+        assert!(OutputFormat::from_name("junit").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{compile_patterns, compile_test_ignores, find_matched_files, ScanCache};
+
+    fn setup(test_ignores: &str) -> (TempDir, Config) {
+        let temp_dir = TempDir::new().unwrap();
+        for file in [
+            "src/foo/driver/bar/one.rs",
+            "src/foo/driver/bar/wip.rs",
+            "src/foo/mock/bar/one.rs",
+            "src/foo/mock/bar/wip.rs",
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, format!(r#"
+test_ignores = {}
+
+[[driver_patterns]]
+pattern = "src/([^/]+)/driver/([^/]+)/([^/]+)\\.rs"
+testcase = "$2_$3"
+
+[[mock_patterns]]
+pattern = "src/([^/]+)/mock/([^/]+)/([^/]+)\\.rs"
+testcase = "$1_$3"
+"#, test_ignores)).unwrap();
+        let config = Config::load(&config_path).unwrap();
+
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn test_test_ignores_exclude_drivers_and_mocks() {
+        let (temp_dir, config) = setup(r#"["/wip\\.rs$"]"#);
+        let mut cache = ScanCache::default();
+
+        let ignore_patterns = compile_test_ignores(&config.test_ignores).unwrap();
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+        let mock_patterns = compile_patterns(&config.mock_patterns).unwrap();
+
+        let matched = find_matched_files(&driver_patterns, &[], &mock_patterns, &ignore_patterns, temp_dir.path(), &mut cache).unwrap();
+
+        assert_eq!(matched.drivers, vec![("src/foo/driver/bar/one.rs".to_string(), 0)]);
+        assert_eq!(matched.mocks, vec!["src/foo/mock/bar/one.rs".to_string()]);
+    }
+
+    #[test]
+    fn test_test_ignores_change_invalidates_scan_cache() {
+        let (temp_dir, config) = setup("[]");
+        let mut cache = ScanCache::default();
+
+        let driver_patterns = compile_patterns(&config.driver_patterns).unwrap();
+
+        let all = find_matched_files(&driver_patterns, &[], &[], &[], temp_dir.path(), &mut cache).unwrap().drivers;
+        let ignores = compile_test_ignores(&["wip".to_string()]).unwrap();
+        let filtered = find_matched_files(&driver_patterns, &[], &[], &ignores, temp_dir.path(), &mut cache).unwrap().drivers;
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(filtered, vec![("src/foo/driver/bar/one.rs".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_test_ignores_invalid_pattern() {
+        let (_temp_dir, config) = setup(r#"["("]"#);
+
+        let error_msg = compile_test_ignores(&config.test_ignores).unwrap_err().to_string();
+        assert!(error_msg.contains("Invalid test_ignores pattern: ("));
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use regex::Regex;
+    use tempfile::TempDir;
+    use crate::config::Config;
+    use crate::test::{index_drivers, resolve_driver_key, PathPattern};
+
+    #[test]
+    fn test_unresolved_driver_falls_back_to_testcase_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("overcode.toml");
+        fs::write(&config_path, r#"
+testcase_template = "{stem}"
+
+[[driver_patterns]]
+pattern = "src/([^/]+)/driver/([^/]+)\\.sh"
+testcase = "$1_$2"
+
+[[driver_patterns]]
+pattern = "tests/[^/]+\\.sh"
+testcase = "$1"
+
+[command.test]
+command = "sh"
+args = ["{driver_file}"]
+"#).unwrap();
+        for file in ["src/a/driver/x.sh", "tests/smoke.sh"] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let config = Config::load(&config_path).unwrap();
+
+        let index = index_drivers(&config, temp_dir.path()).unwrap();
+
+        assert_eq!(index.drivers, vec![
+            ("src/a/driver/x.sh".to_string(), Some("a_x".to_string())),
+            ("tests/smoke.sh".to_string(), Some("smoke".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_testcase_template_satisfies_require_testcase() {
+        let pattern = PathPattern::Regex(Regex::new("tests/.+\\.rs").unwrap());
+        let testcase = "$1".to_string();
+
+        let key = resolve_driver_key("tests/api/login.rs", &[(&pattern, &testcase)], Some("{dir}/{stem}"), true).unwrap();
+
+        assert_eq!(key, Some("tests/api/login".to_string()));
+    }
+}